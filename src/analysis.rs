@@ -0,0 +1,269 @@
+//! Read-only measurements over the whole board.
+
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::Universe;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The four phases of a south-east glider, each fitting a 3×3 box.
+const GLIDER_PHASES: [[&str; 3]; 4] = [
+    [".O.", "..O", "OOO"],
+    ["O.O", ".OO", ".O."],
+    ["..O", "O.O", ".OO"],
+    ["O..", ".OO", "OO."],
+];
+
+/// 64-bit FNV-1a, continuing from `hash`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Every glider phase in all eight orientations as 9-bit row-major masks,
+/// bit `3 * row + col` set for an alive cell.
+fn glider_masks() -> Vec<u16> {
+    let mut masks = Vec::new();
+    for phase in GLIDER_PHASES.iter() {
+        for orientation in 0..8 {
+            let mut mask = 0;
+            for (row, line) in phase.iter().enumerate() {
+                for (col, ch) in line.bytes().enumerate() {
+                    if ch != b'O' {
+                        continue;
+                    }
+                    let (r, c) = if orientation & 4 != 0 {
+                        (col, row)
+                    } else {
+                        (row, col)
+                    };
+                    let r = if orientation & 2 != 0 { 2 - r } else { r };
+                    let c = if orientation & 1 != 0 { 2 - c } else { c };
+                    mask |= 1 << (3 * r + c);
+                }
+            }
+            if !masks.contains(&mask) {
+                masks.push(mask);
+            }
+        }
+    }
+    masks
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// A fast FNV-1a hash of the board's dimensions and cells. Boards that
+    /// compare equal always hash the same, so this can be used to detect
+    /// cycles over long runs without keeping every generation.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash = fnv1a(FNV_OFFSET_BASIS, &self.width.to_le_bytes());
+        hash = fnv1a(hash, &self.height.to_le_bytes());
+        self.row_major_cells()
+            .iter()
+            .fold(hash, |hash, &cell| fnv1a(hash, &[cell as u8]))
+    }
+
+    /// A 32-bit checksum of the board and generation, cheap enough to send
+    /// every frame. Two instances run with the same rule and seed report the
+    /// same checksum each generation, so a mismatch reveals a desync.
+    pub fn frame_checksum(&self) -> u32 {
+        let hash = fnv1a(self.state_hash(), &self.generation.to_le_bytes());
+        (hash ^ (hash >> 32)) as u32
+    }
+
+    /// `[min_row, min_col, max_row, max_col]` of the alive cells, inclusive,
+    /// or `None` when nothing is alive.
+    pub fn live_bounds(&self) -> Option<Box<[u32]>> {
+        let mut bounds: Option<[u32; 4]> = None;
+        for (idx, &cell) in self.cells.iter().enumerate() {
+            if !cell.is_alive() {
+                continue;
+            }
+            let (row, col) = self.coords(idx);
+            bounds = Some(match bounds {
+                None => [row, col, row, col],
+                Some([min_row, min_col, max_row, max_col]) => [
+                    min_row.min(row),
+                    min_col.min(col),
+                    max_row.max(row),
+                    max_col.max(col),
+                ],
+            });
+        }
+        bounds.map(|b| Box::new(b) as Box<[u32]>)
+    }
+
+    /// How many cells have each number of live neighbors: index `k` counts
+    /// the cells with exactly `k`, following the boundary mode. With a radius
+    /// above 1, counts of 8 or more all land in the last entry.
+    pub fn neighbor_histogram(&self) -> Vec<u32> {
+        let mut histogram = vec![0; 9];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let count = self.live_neighbor_count(row, col).min(8);
+                histogram[count as usize] += 1;
+            }
+        }
+        histogram
+    }
+
+    /// Number of separate groups of touching live cells. Cells touch when
+    /// they share an edge, or also a corner if `diagonal` is set; edges wrap
+    /// according to the boundary mode.
+    pub fn count_components(&self, diagonal: bool) -> u32 {
+        let offsets: &[(i64, i64)] = if diagonal {
+            &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ]
+        } else {
+            &[(-1, 0), (0, -1), (0, 1), (1, 0)]
+        };
+        let mut seen = vec![false; self.cells.len()];
+        let mut stack = Vec::new();
+        let mut components = 0;
+        for start in 0..self.cells.len() {
+            if seen[start] || !self.cells[start].is_alive() {
+                continue;
+            }
+            components += 1;
+            seen[start] = true;
+            stack.push(start);
+            while let Some(idx) = stack.pop() {
+                let (row, col) = self.coords(idx);
+                for &(d_r, d_c) in offsets {
+                    if let Some((n_r, n_c)) =
+                        self.boundary_coords(row as i64 + d_r, col as i64 + d_c)
+                    {
+                        let neighbor = self.get_index(n_r, n_c);
+                        if !seen[neighbor] && self.cells[neighbor].is_alive() {
+                            seen[neighbor] = true;
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    /// Fraction of the board that is alive, from `0.0` to `1.0`.
+    pub fn density(&self) -> f64 {
+        self.population() as f64 / self.cells.len() as f64
+    }
+
+    /// Alive fraction of each `tile`×`tile` block, row-major over the grid
+    /// of blocks, for a coarse heat overlay. Blocks along the right and
+    /// bottom edges may be cut short; their fraction is over the cells they
+    /// actually hold.
+    pub fn density_map(&self, tile: u32) -> Result<Vec<f64>, JsValue> {
+        if tile == 0 {
+            return Err(JsValue::from_str(
+                "density_map needs a tile size of at least 1",
+            ));
+        }
+        let tiles_wide = self.width.div_ceil(tile) as usize;
+        let tiles_high = self.height.div_ceil(tile) as usize;
+        let mut alive = vec![0u64; tiles_wide * tiles_high];
+        for (row, col) in self.live_cells() {
+            alive[(row / tile) as usize * tiles_wide + (col / tile) as usize] += 1;
+        }
+        let span = |index: usize, len: u32| (len - index as u32 * tile).min(tile) as f64;
+        Ok(alive
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let cells = span(i / tiles_wide, self.height) * span(i % tiles_wide, self.width);
+                count as f64 / cells
+            })
+            .collect())
+    }
+
+    /// Shannon entropy, in bits, of the distribution of `block`×`block` tile
+    /// patterns when the board is cut into such tiles. `0` means every tile
+    /// looks the same; higher values mean a more varied, chaotic board.
+    /// `block` must evenly divide both dimensions.
+    pub fn block_entropy(&self, block: u32) -> Result<f64, JsValue> {
+        if block == 0 || !self.width.is_multiple_of(block) || !self.height.is_multiple_of(block) {
+            return Err(JsValue::from_str(&format!(
+                "block size {} does not evenly divide a {}x{} board",
+                block, self.width, self.height
+            )));
+        }
+
+        let mut tiles: HashMap<Vec<bool>, u32> = HashMap::new();
+        for top in (0..self.height).step_by(block as usize) {
+            for left in (0..self.width).step_by(block as usize) {
+                let mut tile = Vec::with_capacity(block as usize * block as usize);
+                for row in top..top + block {
+                    for col in left..left + block {
+                        tile.push(self.cells[self.get_index(row, col)].is_alive());
+                    }
+                }
+                *tiles.entry(tile).or_insert(0) += 1;
+            }
+        }
+
+        let total = (self.width / block) as f64 * (self.height / block) as f64;
+        Ok(tiles
+            .values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum())
+    }
+
+    /// Top-left corners of every glider on the board as flat
+    /// `[row0, col0, row1, col1, ...]` pairs in row-major order. A match is a
+    /// 3×3 window holding one of the glider's four phases, in any orientation,
+    /// with nothing alive in the ring of cells around it; cells beyond the
+    /// edge count as dead and windows do not wrap.
+    pub fn find_gliders(&self) -> Vec<u32> {
+        let masks = glider_masks();
+        let alive = |row: i64, col: i64| {
+            row >= 0
+                && col >= 0
+                && self
+                    .try_index(row as u32, col as u32)
+                    .is_some_and(|idx| self.cells[idx].is_alive())
+        };
+
+        let mut found = Vec::new();
+        for top in 0..self.height.saturating_sub(2) as i64 {
+            for left in 0..self.width.saturating_sub(2) as i64 {
+                let mut mask = 0;
+                for r in 0..3 {
+                    for c in 0..3 {
+                        if alive(top + r, left + c) {
+                            mask |= 1 << (3 * r + c);
+                        }
+                    }
+                }
+                if !masks.contains(&mask) {
+                    continue;
+                }
+                let isolated = (-1..4).all(|r| {
+                    (-1..4).all(|c| {
+                        (0..3).contains(&r) && (0..3).contains(&c) || !alive(top + r, left + c)
+                    })
+                });
+                if isolated {
+                    found.push(top as u32);
+                    found.push(left as u32);
+                }
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,68 @@
+//! Headless runner for benchmarking outside the browser:
+//!
+//!     cargo run --release --bin sim -- [width] [height] [generations] [seed] [density]
+//!
+//! Fills a board randomly, ticks it, and prints the final population and how
+//! long the generations took.
+
+use std::env;
+use std::process;
+use std::time::Instant;
+
+use wasm_game_of_life::UniverseBuilder;
+
+fn arg<T: std::str::FromStr>(args: &[String], index: usize, name: &str, default: T) -> T {
+    match args.get(index) {
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            eprintln!("sim: invalid {} {:?}", name, value);
+            process::exit(2);
+        }),
+        None => default,
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let width = arg(&args, 0, "width", 256u32);
+    let height = arg(&args, 1, "height", 256u32);
+    let generations = arg(&args, 2, "generations", 1000u32);
+    let seed = arg(&args, 3, "seed", 42u64);
+    let density = arg(&args, 4, "density", 0.3f64);
+
+    let mut universe = UniverseBuilder::new()
+        .width(width)
+        .height(height)
+        .seed(seed)
+        .density(density)
+        .try_build()
+        .unwrap_or_else(|e| {
+            eprintln!("sim: {}", e);
+            process::exit(2);
+        });
+    // Undo history and population tracking would only add noise to timings.
+    universe.set_history_depth(0);
+    universe.set_history_capacity(0);
+
+    println!(
+        "{}x{} board, seed {}, density {}: initial population {}",
+        width,
+        height,
+        seed,
+        density,
+        universe.population()
+    );
+
+    let start = Instant::now();
+    for _ in 0..generations {
+        universe.tick();
+    }
+    let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+
+    println!(
+        "{} generations in {:.1} ms ({:.3} ms/generation), final population {}",
+        generations,
+        elapsed,
+        elapsed / generations.max(1) as f64,
+        universe.population()
+    );
+}
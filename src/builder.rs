@@ -0,0 +1,117 @@
+//! Chainable construction of a configured `Universe`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{validate_dimensions, BoundaryMode, Layout, Neighborhood, Ruleset, Universe};
+
+/// Collects dimensions, rule, boundary mode and an optional random fill
+/// before building a `Universe`, e.g.
+/// `UniverseBuilder.new().width(256).seed(7n).build()` from JS.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct UniverseBuilder {
+    width: u32,
+    height: u32,
+    rule: String,
+    boundary: BoundaryMode,
+    twist: i32,
+    neighborhood: Neighborhood,
+    layout: Layout,
+    seed: Option<u64>,
+    density: f64,
+}
+
+impl UniverseBuilder {
+    pub fn try_build(&self) -> Result<Universe, String> {
+        validate_dimensions(self.width, self.height)?;
+        let mut universe = Universe::dead(self.width, self.height);
+        universe.rule = Ruleset::parse(&self.rule)?;
+        universe.boundary = self.boundary;
+        universe.twist = self.twist;
+        universe.neighborhood = self.neighborhood;
+        universe.layout = self.layout;
+        if let Some(seed) = self.seed {
+            universe.fill_random(seed, self.density);
+        }
+        Ok(universe)
+    }
+}
+
+#[wasm_bindgen]
+impl UniverseBuilder {
+    /// Starts from an all-dead 128×128 toroidal `B3/S23` board.
+    pub fn new() -> UniverseBuilder {
+        UniverseBuilder {
+            width: 128,
+            height: 128,
+            rule: "B3/S23".to_string(),
+            boundary: BoundaryMode::Toroidal,
+            twist: 0,
+            neighborhood: Neighborhood::Moore,
+            layout: Layout::RowMajor,
+            seed: None,
+            density: 0.5,
+        }
+    }
+
+    pub fn width(mut self, width: u32) -> UniverseBuilder {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> UniverseBuilder {
+        self.height = height;
+        self
+    }
+
+    /// Rule in B/S notation; it is validated by `build`.
+    pub fn rule(mut self, rule: &str) -> UniverseBuilder {
+        self.rule = rule.to_string();
+        self
+    }
+
+    pub fn boundary(mut self, boundary: BoundaryMode) -> UniverseBuilder {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Column shift applied when wrapping vertically; see
+    /// `Universe::set_twist`.
+    pub fn twist(mut self, twist: i32) -> UniverseBuilder {
+        self.twist = twist;
+        self
+    }
+
+    pub fn neighborhood(mut self, neighborhood: Neighborhood) -> UniverseBuilder {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    /// Order of the cells behind `Universe::cells`; row-major by default.
+    pub fn layout(mut self, layout: Layout) -> UniverseBuilder {
+        self.layout = layout;
+        self
+    }
+
+    /// Fills the board randomly from this seed, see `Universe::randomize`.
+    pub fn seed(mut self, seed: u64) -> UniverseBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Chance of each cell starting alive when a seed is set.
+    pub fn density(mut self, density: f64) -> UniverseBuilder {
+        self.density = density;
+        self
+    }
+
+    pub fn build(&self) -> Result<Universe, JsValue> {
+        self.try_build().map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl Default for UniverseBuilder {
+    fn default() -> UniverseBuilder {
+        UniverseBuilder::new()
+    }
+}
@@ -0,0 +1,45 @@
+//! A mask of frozen cells that keep their state through every tick, for
+//! walls and obstacles. Frozen cells still count as neighbors.
+
+use wasm_bindgen::prelude::*;
+
+use crate::Universe;
+
+impl Universe {
+    /// Whether the cell at buffer index `idx` is frozen. The mask stays empty
+    /// until the first cell is frozen, so boards without one pay nothing.
+    pub(crate) fn frozen_at(&self, idx: usize) -> bool {
+        self.frozen.get(idx).copied().unwrap_or(false)
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Freezes or thaws the cell at `(row, col)`. A frozen cell keeps its
+    /// current state through `tick` and noise, though it can still be edited
+    /// directly. Coordinates off the board are ignored.
+    pub fn set_frozen(&mut self, row: u32, col: u32, frozen: bool) {
+        if let Some(idx) = self.try_index(row, col) {
+            if self.frozen.is_empty() {
+                if !frozen {
+                    return;
+                }
+                self.frozen = vec![false; self.cells.len()];
+            }
+            self.frozen[idx] = frozen;
+            // A thawed cell may change even though nothing near it did.
+            self.invalidate_caches();
+        }
+    }
+
+    pub fn is_frozen(&self, row: u32, col: u32) -> bool {
+        self.try_index(row, col)
+            .is_some_and(|idx| self.frozen_at(idx))
+    }
+
+    /// Thaws every cell.
+    pub fn clear_frozen(&mut self) {
+        self.frozen.clear();
+        self.invalidate_caches();
+    }
+}
@@ -0,0 +1,282 @@
+//! Bounded undo/redo stacks of past board states.
+
+use std::collections::VecDeque;
+#[cfg(not(feature = "rayon"))]
+use std::rc::Rc;
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Layout, Universe};
+
+/// How many states are kept for undo unless configured otherwise.
+pub const DEFAULT_DEPTH: usize = 32;
+
+/// Reference-counted cell storage shared between snapshots of the same
+/// state. `Arc` with `rayon`, since the universe must then be `Sync`.
+#[cfg(not(feature = "rayon"))]
+pub type Shared<T> = Rc<T>;
+#[cfg(feature = "rayon")]
+pub type Shared<T> = Arc<T>;
+
+/// A saved board state that `undo`/`redo` can return to.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub width: u32,
+    pub height: u32,
+    pub cells: Shared<[Cell]>,
+    pub layout: Layout,
+    pub generation: u64,
+}
+
+/// An opaque bookmark of a board state taken with `Universe::snapshot`,
+/// lighter than a JSON round trip for "try something, then reset".
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct UniverseSnapshot {
+    inner: Snapshot,
+}
+
+#[wasm_bindgen]
+impl UniverseSnapshot {
+    pub fn width(&self) -> u32 {
+        self.inner.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.inner.height
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.inner.generation
+    }
+}
+
+#[derive(Debug)]
+pub struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    depth: usize,
+}
+
+impl History {
+    pub fn new(depth: usize) -> History {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn clear_redo(&mut self) {
+        self.redo.clear();
+    }
+
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+        self.redo.truncate(depth);
+    }
+
+    /// Records the state from before an edit or tick. Any redo states are
+    /// discarded because they no longer follow from the current one.
+    pub fn push(&mut self, snapshot: Snapshot) {
+        self.redo.clear();
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() >= self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+
+    /// Takes the cells of the snapshot the next `push` would evict, if no
+    /// other snapshot shares them, so their buffer can be reused.
+    pub fn recycle(&mut self, len: usize) -> Option<Shared<[Cell]>> {
+        if self.depth == 0 || self.undo.len() < self.depth {
+            return None;
+        }
+        let front = self.undo.front_mut()?;
+        if front.cells.len() != len || Shared::get_mut(&mut front.cells).is_none() {
+            return None;
+        }
+        self.undo.pop_front().map(|snapshot| snapshot.cells)
+    }
+
+    /// Steps back one state, returning it. `current` is kept for `redo`.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// Reapplies the most recently undone state. `current` is kept for `undo`.
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        Some(next)
+    }
+}
+
+impl Default for History {
+    fn default() -> History {
+        History::new(DEFAULT_DEPTH)
+    }
+}
+
+impl Universe {
+    /// Saves the current state so the edit about to happen can be undone.
+    /// Every edit goes through here, so it also drops the incremental state
+    /// kept by the sparse and tiled ticks and the last generation's list of
+    /// changed cells. Ticks call `push_history` instead.
+    pub(crate) fn record_history(&mut self) {
+        self.push_history();
+        self.invalidate_caches();
+        self.changed.clear();
+        self.stable = false;
+    }
+
+    /// Pushes the current state onto the undo stack ahead of a change to the
+    /// cells, which also ends the sharing of the current cells' storage.
+    pub(crate) fn push_history(&mut self) {
+        let shared = match self.shared_cells.take() {
+            Some(shared) => Some(shared),
+            None if self.history.depth() == 0 => None,
+            // Once the history is full the oldest snapshot's buffer is
+            // reused, so ticking in steady state doesn't allocate.
+            None => Some(match self.history.recycle(self.cells.len()) {
+                Some(mut cells) => {
+                    Shared::get_mut(&mut cells)
+                        .expect("recycled cells are unshared")
+                        .copy_from_slice(&self.cells);
+                    cells
+                }
+                None => Shared::from(&self.cells[..]),
+            }),
+        };
+        match shared {
+            Some(cells) => {
+                let snapshot = self.snapshot_with(cells);
+                self.history.push(snapshot);
+            }
+            None => self.history.clear_redo(),
+        }
+    }
+
+    fn snapshot_with(&self, cells: Shared<[Cell]>) -> Snapshot {
+        Snapshot {
+            width: self.width,
+            height: self.height,
+            cells,
+            layout: self.layout,
+            generation: self.generation,
+        }
+    }
+
+    /// The current cells as shared storage, copied only if they changed
+    /// since the last time they were shared.
+    pub(crate) fn share_cells(&mut self) -> Shared<[Cell]> {
+        match &self.shared_cells {
+            Some(cells) => cells.clone(),
+            None => {
+                let cells: Shared<[Cell]> = Shared::from(&self.cells[..]);
+                self.shared_cells = Some(cells.clone());
+                cells
+            }
+        }
+    }
+
+    /// The current state, sharing cell storage with any other snapshot taken
+    /// since the cells last changed.
+    fn history_snapshot(&mut self) -> Snapshot {
+        let cells = self.share_cells();
+        self.snapshot_with(cells)
+    }
+
+    fn restore_history(&mut self, snapshot: Snapshot) {
+        // Callers check first, before they touch the undo stacks.
+        debug_assert!(!self.ticking);
+        self.width = snapshot.width;
+        self.height = snapshot.height;
+        self.cells.clear();
+        self.cells.extend_from_slice(&snapshot.cells);
+        self.shared_cells = Some(snapshot.cells);
+        self.layout = snapshot.layout;
+        self.generation = snapshot.generation;
+        self.changed.clear();
+        self.stable = false;
+        self.invalidate_caches();
+        self.reset_cell_state();
+        if self.frozen.len() != self.cells.len() {
+            // Undone across a resize; the mask no longer lines up.
+            self.frozen.clear();
+        }
+        self.rebuild_neighbor_tables();
+        self.track_buffer();
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Returns to the state before the last tick or edit. Returns `false` if
+    /// there is nothing left to undo, and fails during a tick.
+    pub fn undo(&mut self) -> Result<bool, JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        let current = self.history_snapshot();
+        match self.history.undo(current) {
+            Some(previous) => {
+                self.restore_history(previous);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Reapplies the last undone tick or edit. Returns `false` if there is
+    /// nothing to redo, and fails during a tick.
+    pub fn redo(&mut self) -> Result<bool, JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        let current = self.history_snapshot();
+        match self.history.redo(current) {
+            Some(next) => {
+                self.restore_history(next);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Bookmarks the current cells, dimensions and generation. Snapshots of
+    /// an unchanged board share one copy of its cells.
+    pub fn snapshot(&mut self) -> UniverseSnapshot {
+        UniverseSnapshot {
+            inner: self.history_snapshot(),
+        }
+    }
+
+    /// Returns to a state saved with `snapshot`, taking on the snapshot's
+    /// dimensions if the board has been resized since. This can be undone.
+    /// Fails during a tick.
+    pub fn restore(&mut self, snapshot: &UniverseSnapshot) -> Result<(), JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        self.record_history();
+        self.restore_history(snapshot.inner.clone());
+        Ok(())
+    }
+
+    /// Caps how many past states are kept for `undo`; `0` disables history.
+    pub fn set_history_depth(&mut self, depth: u32) {
+        self.history.set_depth(depth as usize);
+    }
+}
@@ -0,0 +1,62 @@
+//! JSON save/restore for a whole `Universe`, enabled by the `serde` feature.
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::{validate_dimensions, Cell, Universe};
+
+// Cells are written as bare numbers (0 dead, 1 or 2 for the two species) to
+// keep saved boards compact.
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Cell, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(Cell::Dead),
+            1 => Ok(Cell::Alive),
+            2 => Ok(Cell::AliveB),
+            other => Err(de::Error::custom(format!(
+                "invalid cell state {}, expected 0, 1 or 2",
+                other
+            ))),
+        }
+    }
+}
+
+impl Universe {
+    pub(crate) fn parse_json(json: &str) -> Result<Universe, String> {
+        let mut universe: Universe = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        validate_dimensions(universe.width, universe.height)?;
+        let expected = universe.width as u64 * universe.height as u64;
+        if universe.cells.len() as u64 != expected {
+            return Err(format!(
+                "universe has {} cells but is {}x{}",
+                universe.cells.len(),
+                universe.width,
+                universe.height
+            ));
+        }
+        universe.reset_cell_state();
+        Ok(universe)
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Serializes the full state, including generation, boundary mode and
+    /// rule, as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a Universe always serializes to JSON")
+    }
+
+    /// Restores a universe saved with `to_json`.
+    pub fn from_json(json: &str) -> Result<Universe, JsValue> {
+        Universe::parse_json(json).map_err(|e| JsValue::from_str(&e))
+    }
+}
@@ -1,39 +1,315 @@
+mod analysis;
+mod base64;
+mod builder;
+mod frozen;
+mod history;
+#[cfg(feature = "serde")]
+mod json;
+mod life106;
+mod matrix;
+mod noise;
+mod packed;
+mod patterns;
+mod plaintext;
+mod population;
+mod rle;
+mod rng;
+mod rule;
+mod sparse;
+mod tiles;
+mod timing;
+mod transform;
 mod utils;
 extern crate web_sys;
 
+use std::borrow::Cow;
 use std::fmt::Display;
+#[cfg(not(target_arch = "wasm32"))]
+use std::marker::PhantomData;
+use std::str::FromStr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 use wasm_bindgen::prelude::*;
+#[cfg(target_arch = "wasm32")]
 use web_sys::console;
 
+pub use builder::UniverseBuilder;
+pub use history::UniverseSnapshot;
+use history::{History, Shared};
+use noise::Noise;
+pub use packed::PackedUniverse;
+use population::PopulationHistory;
+use rng::XorShift64;
+pub use rule::Ruleset;
+#[cfg(not(feature = "rayon"))]
+use timing::TickHook;
+use timing::TickTimings;
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+/// Boards with at least this many cells are stepped in parallel when the
+/// `rayon` feature is enabled; below it the thread overhead isn't worth it.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 64 * 1024;
+
+/// Checks that a board of `width`×`height` cells is non-empty and that its
+/// cell count fits in a `u32`, the type `changed_cells` and the other index
+/// lists use, so allocation and indexing can't overflow.
+pub(crate) fn validate_dimensions(width: u32, height: u32) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err(format!(
+            "universe dimensions must be non-zero, got {}x{}",
+            width, height
+        ));
+    }
+    if width as u64 * height as u64 > u32::MAX as u64 {
+        return Err(format!("a {}x{} universe is too large", width, height));
+    }
+    Ok(())
+}
+
 #[wasm_bindgen]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Cell {
     Dead = 0,
+    /// Alive; also the first species, A, when two share the board.
     Alive = 1,
+    /// Alive as the second species, B. Both species follow the same rule and
+    /// count alike as neighbors; they differ only in color and in which one
+    /// a newborn cell joins.
+    AliveB = 2,
 }
 
 impl Cell {
     fn toggle(&mut self) {
         *self = match *self {
-            Cell::Alive => Cell::Dead,
             Cell::Dead => Cell::Alive,
+            Cell::Alive | Cell::AliveB => Cell::Dead,
+        };
+    }
+
+    pub fn is_alive(&self) -> bool {
+        *self != Cell::Dead
+    }
+}
+
+impl From<bool> for Cell {
+    fn from(alive: bool) -> Cell {
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl From<Cell> for bool {
+    fn from(cell: Cell) -> bool {
+        cell.is_alive()
+    }
+}
+
+/// How neighbors are looked up past the edges of the board.
+///
+/// Each neighbor offset is wrapped on its own and counted, even when several
+/// land on the same cell. Along an axis shorter than 3 cells a neighbor can
+/// therefore count twice, and along a 1-cell axis a cell is its own
+/// neighbor: a lone live cell on a 1×1 toroidal board has 8 live neighbors.
+/// This is the usual "infinite tiling" reading of a torus, and every tick
+/// path agrees on it.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryMode {
+    /// Edges wrap around, so the board behaves like a torus.
+    Toroidal = 0,
+    /// Anything off the board is treated as dead.
+    Fixed = 1,
+    /// Left and right edges wrap; off the top or bottom is dead.
+    CylinderHorizontal = 2,
+    /// Top and bottom edges wrap; off the left or right is dead.
+    CylinderVertical = 3,
+}
+
+impl BoundaryMode {
+    /// Whether rows and columns, respectively, wrap around.
+    fn wraps(self) -> (bool, bool) {
+        match self {
+            BoundaryMode::Toroidal => (true, true),
+            BoundaryMode::Fixed => (false, false),
+            BoundaryMode::CylinderHorizontal => (false, true),
+            BoundaryMode::CylinderVertical => (true, false),
+        }
+    }
+
+    /// Maps a possibly out-of-range `(row, col)` onto a `height`×`width`
+    /// grid: wrapped along the axes this mode wraps, `None` when it falls off
+    /// a dead edge. Each trip across the top or bottom edge also slides the
+    /// column by `twist`.
+    fn resolve(
+        self,
+        row: i64,
+        col: i64,
+        height: i64,
+        width: i64,
+        twist: i32,
+    ) -> Option<(i64, i64)> {
+        let (wrap_rows, wrap_cols) = self.wraps();
+        let col = if wrap_rows {
+            col + row.div_euclid(height) * twist as i64
+        } else {
+            col
+        };
+        let axis = |value: i64, len: i64, wrap: bool| {
+            if wrap {
+                Some(value.rem_euclid(len))
+            } else if (0..len).contains(&value) {
+                Some(value)
+            } else {
+                None
+            }
         };
+        Some((axis(row, height, wrap_rows)?, axis(col, width, wrap_cols)?))
+    }
+}
+
+/// Which nearby cells count as neighbors.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighborhood {
+    /// The square around the cell: 8 neighbors at radius 1.
+    #[default]
+    Moore = 0,
+    /// The diamond of cells within `radius` steps along rows and columns: the
+    /// 4 orthogonal neighbors at radius 1, so B/S counts only go up to 4.
+    VonNeumann = 1,
+}
+
+/// How cells are ordered in the buffer behind `cells()`. The coordinate API
+/// is the same either way; only consumers reading the buffer directly need
+/// to know the layout.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Layout {
+    /// Cell `(row, col)` is at `row * width + col`.
+    #[default]
+    RowMajor = 0,
+    /// Cell `(row, col)` is at `col * height + row`, so each column is
+    /// contiguous.
+    ColumnMajor = 1,
+}
+
+impl Layout {
+    fn index(self, width: u32, height: u32, row: u32, column: u32) -> usize {
+        match self {
+            Layout::RowMajor => row as usize * width as usize + column as usize,
+            Layout::ColumnMajor => column as usize * height as usize + row as usize,
+        }
     }
 }
 
 #[wasm_bindgen]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: Vec<Cell>,
+    // Scratch buffer `tick` writes the next generation into before swapping
+    // it with `cells`, so stepping doesn't allocate.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    next_cells: Vec<Cell>,
+    // Indices of the cells that flipped during the most recent generation.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    changed: Vec<u32>,
+    // Whether the board last changed through a generation that flipped no
+    // cells; edits and undo clear it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stable: bool,
+    // How many consecutive generations each cell has been alive for.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ages: Vec<u32>,
+    // Generations-style state of each cell when `num_states > 2`: `1` while
+    // alive, then `2..num_states` while dying. Dead cells not in this range
+    // are fully dead. Unused in the classic two-state mode.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    states: Vec<u8>,
+    num_states: u8,
+    // Neighbors are counted over the `(2r + 1)²` square around each cell.
+    radius: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    neighborhood: Neighborhood,
+    // Wrapped `[previous, current, next]` row and column indices used to
+    // look up neighbors on a torus without modular arithmetic per cell.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    neighbor_rows: Vec<[u32; 3]>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    neighbor_cols: Vec<[u32; 3]>,
+    // Live-neighbor count of every cell, kept up to date by `tick_sparse`.
+    // Only trusted while `counts_valid`; edits and dense ticks clear it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    neighbor_counts: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    counts_valid: bool,
+    // Which tiles had a change last generation, kept by the dense tick so it
+    // can skip quiescent areas. Only trusted while `tiles_valid`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    active_tiles: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tiles_valid: bool,
+    generation: u64,
+    boundary: BoundaryMode,
+    #[cfg_attr(feature = "serde", serde(default))]
+    twist: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    layout: Layout,
+    rule: Ruleset,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: History,
+    // Storage shared by snapshots of the current cells, dropped as soon as
+    // the cells change so the next snapshot takes a fresh copy.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shared_cells: Option<Shared<[Cell]>>,
+    // Address and length of `cells` when `buffer_generation` last changed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    buffer: (usize, usize),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    buffer_generation: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    populations: PopulationHistory,
+    // Cells that came alive and died in the most recent generation.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    births: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    deaths: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    noise: Option<Noise>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_seed: Option<u64>,
+    // Cells `tick` leaves alone, by buffer index; empty when none are frozen.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frozen: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tick_timings: TickTimings,
+    // Neither a JS function nor an arbitrary closure can be shared between
+    // threads, so there are no tick callbacks when ticks may run on several.
+    #[cfg(not(feature = "rayon"))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tick_callback: Option<js_sys::Function>,
+    #[cfg(not(feature = "rayon"))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tick_hook: Option<TickHook>,
+    // Set for the whole of a tick, callback included, so a callback can't
+    // resize the buffers the tick is still working on.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ticking: bool,
 }
 
 impl Universe {
@@ -41,33 +317,567 @@ impl Universe {
         &self.cells
     }
 
+    /// Index into `get_cells()` of `(row, col)`, or `None` when it is off
+    /// the board.
+    pub fn try_index(&self, row: u32, col: u32) -> Option<usize> {
+        if row < self.height && col < self.width {
+            Some(self.get_index(row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the given cells alive. Coordinates off the board are skipped.
     pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.record_history();
         for (row, col) in cells.iter().cloned() {
-            let idx = self.get_index(row, col);
-            self.cells[idx] = Cell::Alive;
+            if let Some(idx) = self.try_index(row, col) {
+                self.write_cell(idx, Cell::Alive);
+            }
+        }
+    }
+
+    /// `(row, col)` of every alive cell, in row-major order.
+    pub fn live_cells(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        (0..self.height).flat_map(move |row| {
+            (0..self.width)
+                .filter(move |&col| self.cells[self.get_index(row, col)].is_alive())
+                .map(move |col| (row, col))
+        })
+    }
+
+    /// An endless iterator that advances one generation per `next()`, exactly
+    /// like `tick`, and yields the population afterwards. Nothing runs until
+    /// it is polled, so `steps().take(100)` or `steps().find(|&p| p == 0)`
+    /// drive the board as far as needed.
+    pub fn steps(&mut self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::from_fn(move || {
+            self.tick();
+            Some(self.population())
+        })
+    }
+
+    /// Ticks `total` times, calling `f` with the board every `every`
+    /// generations and once more after the last one if it didn't fall on a
+    /// multiple, e.g. to write out animation frames. An `every` of `0` only
+    /// calls `f` at the end.
+    pub fn record<F: FnMut(&Universe)>(&mut self, total: u32, every: u32, mut f: F) {
+        for step in 1..=total {
+            self.tick();
+            if step == total || (every != 0 && step.is_multiple_of(every)) {
+                f(self);
+            }
         }
     }
 }
 
 impl Universe {
+    fn dead(width: u32, height: u32) -> Universe {
+        Universe::from_cells(
+            width,
+            height,
+            vec![Cell::Dead; width as usize * height as usize],
+        )
+    }
+
+    /// Wraps an already-built row-major buffer of `width * height` cells,
+    /// such as a parsed pattern, without copying it.
+    fn from_cells(width: u32, height: u32, cells: Vec<Cell>) -> Universe {
+        debug_assert_eq!(cells.len(), width as usize * height as usize);
+        let len = cells.len();
+        let mut universe = Universe {
+            width,
+            height,
+            cells,
+            next_cells: Vec::new(),
+            changed: Vec::new(),
+            stable: false,
+            ages: vec![0; len],
+            states: vec![0; len],
+            num_states: 2,
+            radius: 1,
+            neighborhood: Neighborhood::Moore,
+            neighbor_rows: Vec::new(),
+            neighbor_cols: Vec::new(),
+            neighbor_counts: Vec::new(),
+            counts_valid: false,
+            active_tiles: Vec::new(),
+            tiles_valid: false,
+            generation: 0,
+            boundary: BoundaryMode::Toroidal,
+            twist: 0,
+            layout: Layout::RowMajor,
+            rule: Ruleset::default(),
+            history: History::default(),
+            shared_cells: None,
+            buffer: (0, 0),
+            buffer_generation: 0,
+            populations: PopulationHistory::default(),
+            births: 0,
+            deaths: 0,
+            noise: None,
+            last_seed: None,
+            frozen: Vec::new(),
+            tick_timings: TickTimings::default(),
+            #[cfg(not(feature = "rayon"))]
+            tick_callback: None,
+            #[cfg(not(feature = "rayon"))]
+            tick_hook: None,
+            ticking: false,
+        };
+        universe.rebuild_neighbor_tables();
+        universe.track_buffer();
+        universe
+    }
+
+    /// Runs `tick` with `ticking` set, putting back the previous value even
+    /// if it panics, so a tick started from a tick hook doesn't end the outer
+    /// tick's guard early.
+    fn while_ticking(&mut self, tick: impl FnOnce(&mut Universe)) {
+        let was_ticking = std::mem::replace(&mut self.ticking, true);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| tick(self)));
+        self.ticking = was_ticking;
+        if let Err(panic) = result {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    /// Fails while a tick is in progress, e.g. when called from a tick
+    /// callback, so nothing can change the size of the board mid-tick.
+    fn check_not_ticking(&self) -> Result<(), String> {
+        if self.ticking {
+            return Err("cannot change the board while a tick is in progress".to_string());
+        }
+        Ok(())
+    }
+
+    /// Zeroes the per-cell ages and Generations states, sized to the board.
+    fn reset_cell_state(&mut self) {
+        self.ages.clear();
+        self.ages.resize(self.cells.len(), 0);
+        self.states.clear();
+        self.states.resize(self.cells.len(), 0);
+    }
+
+    /// Writes `cell` at `idx` as an edit. A cell that comes alive or dies
+    /// starts over at age 0, and in Generations mode is plainly alive or
+    /// fully dead rather than dying.
+    fn write_cell(&mut self, idx: usize, cell: Cell) {
+        if self.cells[idx].is_alive() != cell.is_alive() {
+            self.ages[idx] = 0;
+            if let Some(state) = self.states.get_mut(idx) {
+                *state = cell.is_alive() as u8;
+            }
+        }
+        self.cells[idx] = cell;
+    }
+
+    /// Writes a `width`×`height` row-major pattern, whose cell `i` is
+    /// `cell_at(i)`, with its top-left corner at `(top, left)`, wrapping or
+    /// clipping at the edges. Stops early if `cell_at` runs out of cells.
+    fn stamp(
+        &mut self,
+        top: u32,
+        left: u32,
+        width: u32,
+        height: u32,
+        cell_at: impl Fn(usize) -> Option<Cell>,
+    ) {
+        self.record_history();
+        for row in 0..height {
+            for col in 0..width {
+                let cell = match cell_at(row as usize * width as usize + col as usize) {
+                    Some(cell) => cell,
+                    None => return,
+                };
+                let target =
+                    self.boundary_coords(top as i64 + row as i64, left as i64 + col as i64);
+                if let Some((r, c)) = target {
+                    let idx = self.get_index(r, c);
+                    self.write_cell(idx, cell);
+                }
+            }
+        }
+    }
+
+    /// Whether a dead cell is still in one of the Generations dying states,
+    /// which blocks it from being born.
+    fn is_dying(&self, idx: usize) -> bool {
+        self.num_states > 2 && self.states[idx] >= 2
+    }
+
+    fn write_grid(&self, f: &mut dyn std::fmt::Write, alive: char, dead: char) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let cell = self.cells[self.get_index(row, col)];
+                let symbol = if cell.is_alive() { alive } else { dead };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+
+    /// The cells in row-major order, copied only when the board uses another
+    /// layout. Formats and pixel buffers are always row-major.
+    fn row_major_cells(&self) -> Cow<'_, [Cell]> {
+        match self.layout {
+            Layout::RowMajor => Cow::Borrowed(&self.cells),
+            Layout::ColumnMajor => {
+                let mut cells = Vec::with_capacity(self.cells.len());
+                for row in 0..self.height {
+                    for col in 0..self.width {
+                        cells.push(self.cells[self.get_index(row, col)]);
+                    }
+                }
+                Cow::Owned(cells)
+            }
+        }
+    }
+
+    fn fill_random(&mut self, seed: u64, alive_probability: f64) {
+        self.last_seed = Some(seed);
+        let p = alive_probability.clamp(0.0, 1.0);
+        let mut rng = XorShift64::new(seed);
+        // Drawn in row-major order so a seed gives the same board in any
+        // layout.
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                self.cells[idx] = Cell::from(rng.next_f64() < p);
+            }
+        }
+        self.reset_cell_state();
+        self.generation = 0;
+    }
+
+    fn wrapped_neighbors(len: u32) -> Vec<[u32; 3]> {
+        (0..len)
+            .map(|i| {
+                let prev = if i == 0 { len - 1 } else { i - 1 };
+                let next = if i + 1 == len { 0 } else { i + 1 };
+                [prev, i, next]
+            })
+            .collect()
+    }
+
+    fn neighbor_tables_valid(&self) -> bool {
+        self.neighbor_rows.len() == self.height as usize
+            && self.neighbor_cols.len() == self.width as usize
+    }
+
+    fn rebuild_neighbor_tables(&mut self) {
+        if !self.neighbor_tables_valid() {
+            self.neighbor_rows = Universe::wrapped_neighbors(self.height);
+            self.neighbor_cols = Universe::wrapped_neighbors(self.width);
+        }
+    }
+
     fn get_index(&self, row: u32, column: u32) -> usize {
-        (row * self.width + column) as usize
+        self.layout.index(self.width, self.height, row, column)
+    }
+
+    /// The `(row, col)` stored at buffer index `idx`.
+    fn coords(&self, idx: usize) -> (u32, u32) {
+        match self.layout {
+            Layout::RowMajor => (
+                (idx / self.width as usize) as u32,
+                (idx % self.width as usize) as u32,
+            ),
+            Layout::ColumnMajor => (
+                (idx % self.height as usize) as u32,
+                (idx / self.height as usize) as u32,
+            ),
+        }
     }
 
-    fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
-        let mut count: u8 = 0;
-        for d_r in -1..=1 {
-            for d_c in -1..=1 {
+    /// Maps a possibly off-board coordinate onto the board according to the
+    /// boundary mode: wrapped along the wrapping axes, `None` when it falls
+    /// off a dead edge.
+    fn boundary_coords(&self, row: i64, column: i64) -> Option<(u32, u32)> {
+        self.boundary
+            .resolve(
+                row,
+                column,
+                self.height as i64,
+                self.width as i64,
+                self.twist,
+            )
+            .map(|(row, column)| (row as u32, column as u32))
+    }
+
+    /// Reallocates the board at the new size, moving old cell `(row, col)`
+    /// to `(row + d_row, col + d_col)` and dropping whatever lands outside.
+    fn resize_offset(
+        &mut self,
+        width: u32,
+        height: u32,
+        d_row: i64,
+        d_col: i64,
+    ) -> Result<(), JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        self.record_history();
+        let mut cells = vec![Cell::Dead; width as usize * height as usize];
+        let mut ages = vec![0; cells.len()];
+        let mut states = vec![0; cells.len()];
+        let mut frozen = vec![
+            false;
+            if self.frozen.is_empty() {
+                0
+            } else {
+                cells.len()
+            }
+        ];
+        for row in 0..self.height {
+            let new_row = row as i64 + d_row;
+            if !(0..height as i64).contains(&new_row) {
+                continue;
+            }
+            for col in 0..self.width {
+                let new_col = col as i64 + d_col;
+                if !(0..width as i64).contains(&new_col) {
+                    continue;
+                }
+                let old = self.get_index(row, col);
+                let new = self
+                    .layout
+                    .index(width, height, new_row as u32, new_col as u32);
+                cells[new] = self.cells[old];
+                ages[new] = self.ages[old];
+                states[new] = self.states[old];
+                if !frozen.is_empty() {
+                    frozen[new] = self.frozen[old];
+                }
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.ages = ages;
+        self.states = states;
+        self.frozen = frozen;
+        self.changed.clear();
+        self.tick_timings.reset();
+        self.rebuild_neighbor_tables();
+        self.track_buffer();
+        Ok(())
+    }
+
+    /// Stamps a pattern drawn as rows of `O` (alive) and `.` (dead).
+    fn insert_rows(&mut self, row: u32, col: u32, rows: &[&str]) {
+        let width = rows[0].len() as u32;
+        let cells: Vec<u8> = rows
+            .iter()
+            .flat_map(|line| line.bytes().map(|b| (b == b'O') as u8))
+            .collect();
+        self.insert_pattern(row, col, width, rows.len() as u32, &cells);
+    }
+
+    fn live_neighbor_count(&self, row: u32, column: u32) -> u32 {
+        self.count_neighbors(row, column, |cell| cell.is_alive())
+    }
+
+    /// The species a cell born at `(row, col)` joins: whichever has more live
+    /// neighbors, with ties going to species A.
+    fn birth_species(&self, row: u32, column: u32) -> Cell {
+        let b = self.count_neighbors(row, column, |cell| cell == Cell::AliveB);
+        let a = self.count_neighbors(row, column, |cell| cell == Cell::Alive);
+        if b > a {
+            Cell::AliveB
+        } else {
+            Cell::Alive
+        }
+    }
+
+    /// How many neighbors of `(row, column)` satisfy `matches`, under the
+    /// current radius and boundary mode.
+    fn count_neighbors(&self, row: u32, column: u32, matches: impl Fn(Cell) -> bool) -> u32 {
+        if self.radius == 1
+            && self.neighborhood == Neighborhood::Moore
+            && self.boundary == BoundaryMode::Toroidal
+            && self.twist == 0
+            && self.neighbor_tables_valid()
+        {
+            let rows = &self.neighbor_rows[row as usize];
+            let cols = &self.neighbor_cols[column as usize];
+            let mut count = 0;
+            for (i, &n_r) in rows.iter().enumerate() {
+                for (j, &n_c) in cols.iter().enumerate() {
+                    if i != 1 || j != 1 {
+                        count += matches(self.cells[self.get_index(n_r, n_c)]) as u32;
+                    }
+                }
+            }
+            return count;
+        }
+
+        let radius = self.radius as i64;
+        let mut count = 0;
+        for d_r in -radius..=radius {
+            for d_c in -radius..=radius {
                 if d_r == 0 && d_c == 0 {
                     continue;
                 }
-                let n_r: u32 = (row as i32 + d_r + self.height as i32) as u32 % self.height;
-                let n_c: u32 = (column as i32 + d_c + self.width as i32) as u32 % self.width;
-                count += self.cells[self.get_index(n_r, n_c)] as u8;
+                if self.neighborhood == Neighborhood::VonNeumann && d_r.abs() + d_c.abs() > radius {
+                    continue;
+                }
+                if let Some((n_r, n_c)) =
+                    self.boundary_coords(row as i64 + d_r, column as i64 + d_c)
+                {
+                    count += matches(self.cells[self.get_index(n_r, n_c)]) as u32;
+                }
             }
         }
         count
     }
+
+    fn next_state(&self, row: u32, column: u32) -> Cell {
+        let idx = self.get_index(row, column);
+        let live_neighbors = self.live_neighbor_count(row, column) as usize;
+        // B/S rules only name counts up to 8; anything higher, possible with a
+        // larger radius, never births or survives.
+        let cell = self.cells[idx];
+        if self.frozen_at(idx) {
+            cell
+        } else if cell.is_alive() {
+            if self.rule.survive.get(live_neighbors) == Some(&true) {
+                cell
+            } else {
+                Cell::Dead
+            }
+        } else if !self.is_dying(idx) && self.rule.birth.get(live_neighbors) == Some(&true) {
+            self.birth_species(row, column)
+        } else {
+            Cell::Dead
+        }
+    }
+
+    /// Computes one contiguous run of the next generation: row `line` in the
+    /// row-major layout, column `line` in the column-major one.
+    fn fill_line(&self, line: usize, next_line: &mut [Cell]) {
+        for (offset, cell) in next_line.iter_mut().enumerate() {
+            let (row, column) = match self.layout {
+                Layout::RowMajor => (line as u32, offset as u32),
+                Layout::ColumnMajor => (offset as u32, line as u32),
+            };
+            *cell = self.next_state(row, column);
+        }
+    }
+
+    /// Whether `fill_next` splits the board across threads.
+    fn steps_in_parallel(&self) -> bool {
+        #[cfg(feature = "rayon")]
+        {
+            self.cells.len() >= PARALLEL_THRESHOLD
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            false
+        }
+    }
+
+    fn fill_next(&self, next_gen: &mut [Cell]) {
+        let line_len = match self.layout {
+            Layout::RowMajor => self.width,
+            Layout::ColumnMajor => self.height,
+        } as usize;
+        if line_len == 0 {
+            return;
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            if self.steps_in_parallel() {
+                use rayon::prelude::*;
+
+                next_gen
+                    .par_chunks_mut(line_len)
+                    .enumerate()
+                    .for_each(|(line, next_line)| self.fill_line(line, next_line));
+                return;
+            }
+        }
+
+        for (line, next_line) in next_gen.chunks_mut(line_len).enumerate() {
+            self.fill_line(line, next_line);
+        }
+    }
+
+    fn advance(&mut self) {
+        self.rebuild_neighbor_tables();
+        self.states.resize(self.cells.len(), 0);
+        let mut next_gen = std::mem::take(&mut self.next_cells);
+        next_gen.resize(self.cells.len(), Cell::Dead);
+        if self.can_skip_tiles() {
+            self.fill_next_tiles(&mut next_gen);
+        } else {
+            self.fill_next(&mut next_gen);
+        }
+
+        self.changed.clear();
+        self.ages.resize(self.cells.len(), 0);
+        for (idx, &new) in next_gen.iter().enumerate() {
+            let old = self.cells[idx];
+            if old != new {
+                self.changed.push(idx as u32);
+            }
+            self.advance_cell_state(idx, old, new);
+        }
+
+        // Copy rather than swap so `cells()` keeps pointing at the same buffer
+        // from one generation to the next.
+        self.cells.copy_from_slice(&next_gen);
+        self.next_cells = next_gen;
+        self.counts_valid = false;
+        self.update_active_tiles();
+        self.generation += 1;
+    }
+
+    /// Forgets the incremental state the sparse and tiled ticks keep, after
+    /// the cells or anything that decides the next generation changed.
+    fn invalidate_caches(&mut self) {
+        self.counts_valid = false;
+        self.tiles_valid = false;
+    }
+
+    /// Bumps `buffer_generation` if `cells` moved or changed length since it
+    /// was last checked; called after anything that may reallocate it.
+    fn track_buffer(&mut self) {
+        let buffer = (self.cells.as_ptr() as usize, self.cells.len());
+        if buffer != self.buffer {
+            self.buffer = buffer;
+            self.buffer_generation = self.buffer_generation.wrapping_add(1);
+        }
+    }
+
+    /// Updates the age and Generations state of a cell going from `old` to
+    /// `new` in one generation.
+    fn advance_cell_state(&mut self, idx: usize, old: Cell, new: Cell) {
+        self.ages[idx] = match (old.is_alive(), new.is_alive()) {
+            (true, true) => self.ages[idx].saturating_add(1),
+            _ => 0,
+        };
+        if self.num_states > 2 {
+            self.states[idx] = match (old.is_alive(), new.is_alive()) {
+                (_, true) => 1,
+                (true, false) => 2,
+                (false, false) => match self.states[idx] {
+                    state if state >= 2 && state + 1 < self.num_states => state + 1,
+                    _ => 0,
+                },
+            };
+        }
+    }
+
+    /// Bookkeeping after each generation a tick method runs, but not the
+    /// trial steps `detect_period` takes.
+    fn end_generation(&mut self) {
+        self.apply_noise();
+        self.record_population();
+        self.stable = self.changed.is_empty();
+    }
 }
 
 #[wasm_bindgen]
@@ -76,108 +886,1106 @@ impl Universe {
         self.width
     }
 
-    pub fn set_width(&mut self, width: u32) {
+    /// Changes the board size with a single reallocation, keeping every cell
+    /// that still falls inside the new bounds. Newly exposed cells are dead.
+    ///
+    /// Unlike `set_width`/`set_height`, which start over with an empty board,
+    /// this preserves the existing pattern anchored at the top-left corner.
+    /// Like the other methods that can change the board's size, this fails
+    /// while a tick is in progress, e.g. when called from a tick callback.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), JsValue> {
+        self.resize_offset(width, height, 0, 0)
+    }
+
+    /// Like `resize`, but keeps the old board centered in the new one: it
+    /// gains or loses the same number of rows at the top and bottom, and of
+    /// columns at the left and right. When the difference is odd the extra
+    /// row or column goes to the bottom or right.
+    pub fn resize_centered(&mut self, width: u32, height: u32) -> Result<(), JsValue> {
+        let d_row = (height as i64 - self.height as i64) / 2;
+        let d_col = (width as i64 - self.width as i64) / 2;
+        self.resize_offset(width, height, d_row, d_col)
+    }
+
+    /// Crops the board to the bounding box of its live cells with `margin`
+    /// dead cells on every side, e.g. right before `to_rle`. An empty board
+    /// is left as it is.
+    pub fn trim(&mut self, margin: u32) -> Result<(), JsValue> {
+        let bounds = match self.live_bounds() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        let (min_row, min_col, max_row, max_col) = (bounds[0], bounds[1], bounds[2], bounds[3]);
+        let size = |min: u32, max: u32| {
+            (max - min + 1)
+                .checked_add(margin)
+                .and_then(|size| size.checked_add(margin))
+                .ok_or_else(|| JsValue::from_str(&format!("a margin of {} is too large", margin)))
+        };
+        let (width, height) = (size(min_col, max_col)?, size(min_row, max_row)?);
+        self.resize_offset(
+            width,
+            height,
+            margin as i64 - min_row as i64,
+            margin as i64 - min_col as i64,
+        )
+    }
+
+    /// Sets the width and starts over with an all-dead board. Fails on a
+    /// zero or oversized width, or during a tick.
+    pub fn set_width(&mut self, width: u32) -> Result<(), JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        validate_dimensions(width, self.height).map_err(|e| JsValue::from_str(&e))?;
+        self.record_history();
         self.width = width;
-        self.cells = (0..(self.width * self.height))
-            .map(|_| Cell::Dead)
-            .collect();
+        self.cells = vec![Cell::Dead; self.width as usize * self.height as usize];
+        self.reset_cell_state();
+        self.frozen.clear();
+        self.changed.clear();
+        self.tick_timings.reset();
+        self.rebuild_neighbor_tables();
+        self.track_buffer();
+        self.generation = 0;
+        Ok(())
     }
 
-    pub fn set_height(&mut self, height: u32) {
+    /// Sets the height and starts over with an all-dead board. Fails on a
+    /// zero or oversized height, or during a tick.
+    pub fn set_height(&mut self, height: u32) -> Result<(), JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        validate_dimensions(self.width, height).map_err(|e| JsValue::from_str(&e))?;
+        self.record_history();
         self.height = height;
-        self.cells = (0..(self.width * self.height))
-            .map(|_| Cell::Dead)
-            .collect();
+        self.cells = vec![Cell::Dead; self.width as usize * self.height as usize];
+        self.reset_cell_state();
+        self.frozen.clear();
+        self.changed.clear();
+        self.tick_timings.reset();
+        self.rebuild_neighbor_tables();
+        self.track_buffer();
+        self.generation = 0;
+        Ok(())
     }
 
     pub fn height(&self) -> u32 {
         self.height
     }
 
+    /// Pointer to one byte per cell, ordered according to `layout()`.
     pub fn cells(&self) -> *const Cell {
         self.cells.as_ptr()
     }
 
-    pub fn tick(&mut self) -> () {
-        let _timer = Timer::new("universe_tick");
-        let mut next_gen = self.cells.clone();
+    /// A counter that changes whenever the buffer behind `cells()` is
+    /// reallocated, e.g. by `resize`, `set_width`, a transform or an undo
+    /// across sizes. Ticks and edits keep the same buffer. A typed-array view
+    /// over wasm memory only needs rebuilding when this differs from the
+    /// value it was built with, or when growing memory detached it.
+    pub fn buffer_generation(&self) -> u32 {
+        self.buffer_generation
+    }
 
-        for row in 0..self.height {
-            for column in 0..self.width {
-                let idx: usize = self.get_index(row, column);
-                let cell: Cell = self.cells[idx];
-                let live_neighbors: u8 = self.live_neighbor_count(row, column);
-
-                next_gen[idx] = match (cell, live_neighbors) {
-                    (Cell::Alive, x) if x < 2 => Cell::Dead,
-                    (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-                    (Cell::Alive, x) if x > 3 => Cell::Dead,
-                    (Cell::Dead, 3) => Cell::Alive,
-                    (otherwise, _) => otherwise,
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Number of cell bytes behind the `cells` pointer, for sizing a
+    /// `Uint8Array` view without assuming the layout.
+    pub fn cells_len(&self) -> u32 {
+        self.cells.len() as u32
+    }
+
+    /// Writable pointer to the same bytes as `cells()`, for editors that paint
+    /// straight into wasm memory. The board as it was when the pointer was
+    /// first taken becomes an undo step at the next `mark_dirty()`. Keeping
+    /// it means the first fetch after each tick or edit copies the board;
+    /// later fetches until the next change reuse that copy.
+    ///
+    /// The caller takes on what the type system can't check:
+    /// - only write within the first `cells_len()` bytes, at the indices
+    ///   `layout()` assigns;
+    /// - only write `0` (dead), `1` (alive) or `2` (species B); any other byte
+    ///   is not a valid `Cell` and is undefined behavior;
+    /// - don't write while another method is running, and fetch the pointer
+    ///   again whenever `buffer_generation()` changes;
+    /// - call `mark_dirty()` after a batch of writes, before the next tick
+    ///   or any other call that reads the cells.
+    pub fn cells_mut_ptr(&mut self) -> *mut Cell {
+        // Keeps a copy of the cells from before any writes, which
+        // `mark_dirty` then records.
+        self.share_cells();
+        self.cells.as_mut_ptr()
+    }
+
+    /// Tells the board its cells were changed behind its back, through
+    /// `cells_mut_ptr`: records the board from before the writes for
+    /// `undo`, and makes the sparse and tiled ticks recount.
+    pub fn mark_dirty(&mut self) {
+        self.record_history();
+    }
+
+    pub fn boundary_mode(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn set_boundary_mode(&mut self, mode: BoundaryMode) {
+        self.boundary = mode;
+        self.invalidate_caches();
+    }
+
+    pub fn twist(&self) -> i32 {
+        self.twist
+    }
+
+    /// Makes wrapping across the top or bottom edge also shift the column by
+    /// `twist` cells, for a twisted torus. Only matters when rows wrap, i.e.
+    /// with `Toroidal` or `CylinderVertical`; `0` is the plain wrap.
+    pub fn set_twist(&mut self, twist: i32) {
+        self.twist = twist;
+        self.invalidate_caches();
+    }
+
+    /// Switches to another Life-like rule given in B/S notation, such as
+    /// `B36/S23` (HighLife) or `B2/S` (Seeds).
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), JsValue> {
+        self.rule = Ruleset::parse(rule).map_err(|e| JsValue::from_str(&e))?;
+        self.invalidate_caches();
+        Ok(())
+    }
+
+    /// The current rule in canonical B/S notation, e.g. `B36/S23`.
+    pub fn rule_string(&self) -> String {
+        self.rule.to_string()
+    }
+
+    /// Switches to a Generations-style rule where a cell that dies spends
+    /// `num_states - 2` generations dying before it is fully dead and can be
+    /// born again. Dying cells don't count as live neighbors. `2` (the
+    /// default) is classic two-state Life.
+    pub fn set_num_states(&mut self, num_states: u8) {
+        self.num_states = num_states.max(2);
+        self.states.clear();
+        self.states.resize(self.cells.len(), 0);
+        self.invalidate_caches();
+    }
+
+    pub fn num_states(&self) -> u8 {
+        self.num_states
+    }
+
+    /// The Generations state of a cell: `0` dead, `1` alive, and
+    /// `2..num_states` while dying.
+    pub fn get_state(&self, row: u32, col: u32) -> u8 {
+        let idx = match self.try_index(row, col) {
+            Some(idx) => idx,
+            None => return 0,
+        };
+        if self.cells[idx].is_alive() {
+            1
+        } else if self.is_dying(idx) {
+            self.states[idx]
+        } else {
+            0
+        }
+    }
+
+    /// Counts live neighbors over a larger neighborhood: every cell within
+    /// `radius` rows and columns for `Moore`, or within `radius` steps for
+    /// `VonNeumann`. The default radius is 1, the usual eight neighbors, and
+    /// it is never less than 1.
+    pub fn set_radius(&mut self, radius: u32) {
+        self.radius = radius.max(1);
+        self.invalidate_caches();
+    }
+
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// Switches between the square `Moore` neighborhood (the default) and the
+    /// diamond-shaped `VonNeumann` one.
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+        self.invalidate_caches();
+    }
+
+    /// Number of times `tick` has been called since the board was last reset.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of alive cells on the board.
+    pub fn population(&self) -> u32 {
+        self.cells
+            .iter()
+            .fold(0, |count, &cell| count + cell.is_alive() as u32)
+    }
+
+    /// Whether no cell is alive, stopping at the first live one found.
+    pub fn is_empty(&self) -> bool {
+        !self.cells.iter().any(|cell| cell.is_alive())
+    }
+
+    /// Whether every cell is alive, stopping at the first dead one found.
+    pub fn is_full(&self) -> bool {
+        self.cells.iter().all(|cell| cell.is_alive())
+    }
+
+    /// Indices into `cells()` of the cells that changed state during the most
+    /// recent generation, for repainting only what flipped; empty after an
+    /// edit. With the default row-major layout an index is `row * width + col`.
+    pub fn changed_cells(&self) -> Vec<u32> {
+        self.changed.clone()
+    }
+
+    /// The changes from the most recent generation packed one per number as
+    /// `(index << 1) | new_state`, so a consumer gets each cell's index and
+    /// whether it is now alive without a separate lookup. The shift drops the
+    /// top bit of indices from 2^31 up, so boards of more than 2^31 cells,
+    /// too big for wasm32 memory anyway, should use `changed_cells`.
+    pub fn diff_packed(&self) -> Vec<u32> {
+        self.changed
+            .iter()
+            .map(|&idx| (idx << 1) | self.cells[idx as usize].is_alive() as u32)
+            .collect()
+    }
+
+    /// Indices into `cells()` of the cells whose state differs between this
+    /// board and `other`, in increasing order. The boards must be the same
+    /// size; their layouts may differ.
+    pub fn diff_against(&self, other: &Universe) -> Result<Vec<u32>, JsValue> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(JsValue::from_str(&format!(
+                "cannot compare a {}x{} board with a {}x{} one",
+                self.width, self.height, other.width, other.height
+            )));
+        }
+        Ok(self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|&(idx, &cell)| {
+                let (row, col) = self.coords(idx);
+                other.cells[other.get_index(row, col)] != cell
+            })
+            .map(|(idx, _)| idx as u32)
+            .collect())
+    }
+
+    /// Whether the most recent `tick` left the board unchanged, i.e. it has
+    /// settled into a still life. False before the first tick and after any
+    /// edit or undo, until a tick shows the board is still.
+    pub fn is_stable(&self) -> bool {
+        self.stable
+    }
+
+    /// Ticks until a generation changes nothing or `max_steps` generations
+    /// have run, returning how many were run.
+    pub fn tick_until_stable(&mut self, max_steps: u32) -> u32 {
+        if max_steps == 0 {
+            return 0;
+        }
+        let mut steps = 0;
+        self.while_ticking(|universe| {
+            let timer = universe.tick_timer("universe_tick_until_stable");
+            universe.push_history();
+            while steps < max_steps {
+                universe.advance();
+                universe.end_generation();
+                steps += 1;
+                if universe.changed.is_empty() {
+                    break;
                 }
             }
+            universe.finish_tick(timer);
+        });
+        steps
+    }
+
+    /// Smallest number of generations, up to `max_period`, after which the
+    /// board returns to its current state. The board is restored afterwards.
+    pub fn detect_period(&mut self, max_period: u32) -> Option<u32> {
+        let start = self.cells.clone();
+        let ages = self.ages.clone();
+        let states = self.states.clone();
+        let next_cells = self.next_cells.clone();
+        let generation = self.generation;
+        let changed = self.changed.clone();
+
+        let mut period = None;
+        for steps in 1..=max_period {
+            self.advance();
+            if self.cells == start {
+                period = Some(steps);
+                break;
+            }
         }
 
-        self.cells = next_gen;
+        self.cells.copy_from_slice(&start);
+        self.ages.clear();
+        self.ages.extend_from_slice(&ages);
+        self.states.clear();
+        self.states.extend_from_slice(&states);
+        self.next_cells.clear();
+        self.next_cells.extend_from_slice(&next_cells);
+        self.generation = generation;
+        self.changed = changed;
+        self.invalidate_caches();
+        period
+    }
+
+    pub fn tick(&mut self) {
+        self.while_ticking(|universe| {
+            let timer = universe.tick_timer("universe_tick");
+            universe.push_history();
+            universe.advance();
+            universe.end_generation();
+            universe.finish_tick(timer);
+        });
+    }
+
+    /// Builds the neighbor lookup tables and sizes the scratch buffers for
+    /// the current dimensions, so the first `tick` after a resize doesn't pay
+    /// for it mid-animation. Does nothing if they are already up to date.
+    pub fn prepare(&mut self) {
+        self.rebuild_neighbor_tables();
+        let len = self.cells.len();
+        self.next_cells.resize(len, Cell::Dead);
+        self.ages.resize(len, 0);
+        self.states.resize(len, 0);
+    }
+
+    /// Runs one generation like `tick` and returns whether any cell changed,
+    /// so an animation loop can stop once a still life is reached.
+    pub fn step(&mut self) -> bool {
+        self.tick();
+        !self.changed.is_empty()
+    }
+
+    /// Advances `steps` generations in a single call, avoiding a wasm
+    /// boundary crossing per generation.
+    pub fn tick_many(&mut self, steps: u32) {
+        if steps == 0 {
+            return;
+        }
+        self.while_ticking(|universe| {
+            let timer = universe.tick_timer("universe_tick_many");
+            universe.push_history();
+            for _ in 0..steps {
+                universe.advance();
+                universe.end_generation();
+            }
+            universe.finish_tick(timer);
+        });
+    }
+
+    /// Advances only the cells inside a `height`×`width` rectangle with its
+    /// top-left corner at `(top, left)`, clipped to the board. Neighbors just
+    /// outside the box are read, following the boundary mode, but never
+    /// changed, so this is an approximation of a full `tick` for boards
+    /// whose activity is confined to one area. Counts as a generation.
+    pub fn tick_region(&mut self, top: u32, left: u32, height: u32, width: u32) {
+        let bottom = top.saturating_add(height).min(self.height);
+        let right = left.saturating_add(width).min(self.width);
+        if top >= bottom || left >= right {
+            return;
+        }
+
+        self.while_ticking(|universe| {
+            let timer = universe.tick_timer("universe_tick_region");
+            universe.record_history();
+            universe.rebuild_neighbor_tables();
+            universe.states.resize(universe.cells.len(), 0);
+            universe.ages.resize(universe.cells.len(), 0);
+            let mut next = Vec::with_capacity((bottom - top) as usize * (right - left) as usize);
+            for row in top..bottom {
+                for col in left..right {
+                    next.push(universe.next_state(row, col));
+                }
+            }
+
+            universe.changed.clear();
+            let mut next = next.into_iter();
+            for row in top..bottom {
+                for col in left..right {
+                    let idx = universe.get_index(row, col);
+                    let (old, new) = (
+                        universe.cells[idx],
+                        next.next().expect("one state per cell"),
+                    );
+                    if old != new {
+                        universe.changed.push(idx as u32);
+                    }
+                    universe.advance_cell_state(idx, old, new);
+                    universe.cells[idx] = new;
+                }
+            }
+            universe.changed.sort_unstable();
+            universe.generation += 1;
+            universe.record_population();
+            universe.stable = universe.changed.is_empty();
+            universe.finish_tick(timer);
+        });
     }
 
+    /// An all-dead 128×128 board, ready to draw on.
     pub fn new() -> Universe {
-        let width = 128;
-        let height = 128;
+        utils::set_panic_hook();
+        Universe::dead(128, 128)
+    }
 
-        let cells = (0..width * height)
-            .map(|x| {
-                if x % 2 == 0 || x % 7 == 0 {
-                    Cell::Alive
-                } else {
-                    Cell::Dead
+    /// A 128×128 board with the book's starter pattern, where every cell
+    /// whose index is a multiple of 2 or 7 is alive.
+    pub fn new_demo() -> Universe {
+        let mut universe = Universe::new();
+        for (idx, cell) in universe.cells.iter_mut().enumerate() {
+            *cell = Cell::from(idx % 2 == 0 || idx % 7 == 0);
+        }
+        universe
+    }
+
+    /// Creates an all-dead board of the given size, for callers that want to
+    /// draw their own starting pattern.
+    pub fn with_dimensions(width: u32, height: u32) -> Result<Universe, JsValue> {
+        utils::set_panic_hook();
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Universe::dead(width, height))
+    }
+
+    /// Kills every cell in place, keeping the current dimensions.
+    pub fn clear(&mut self) {
+        self.record_history();
+        for cell in self.cells.iter_mut() {
+            *cell = Cell::Dead;
+        }
+        self.reset_cell_state();
+        self.frozen.clear();
+        self.tick_timings.reset();
+        self.generation = 0;
+    }
+
+    /// Flips every cell in place. The generation is unchanged since no tick
+    /// happened.
+    pub fn invert(&mut self) {
+        self.record_history();
+        for cell in self.cells.iter_mut() {
+            cell.toggle();
+        }
+        self.reset_cell_state();
+    }
+
+    /// Fills the board from `seed`, making each cell alive with probability
+    /// `alive_probability` (clamped to `[0.0, 1.0]`). The same seed and
+    /// dimensions always produce the same board.
+    pub fn randomize(&mut self, seed: u64, alive_probability: f64) {
+        self.record_history();
+        self.fill_random(seed, alive_probability);
+    }
+
+    /// Like `randomize`, but with a fresh seed each call for a board nobody
+    /// needs to reproduce. `last_seed` reports the seed it picked, so a board
+    /// worth keeping can be recreated with `randomize`.
+    pub fn randomize_entropy(&mut self, alive_probability: f64) {
+        self.randomize(rng::entropy_seed(), alive_probability);
+    }
+
+    /// The seed of the most recent random fill, from `randomize`,
+    /// `randomize_entropy` or the builder, or `None` if there was none.
+    pub fn last_seed(&self) -> Option<u64> {
+        self.last_seed
+    }
+
+    /// Like `randomize`, but returns the resulting population so callers can
+    /// regenerate until they like the fill without another call.
+    pub fn randomize_from_density(&mut self, seed: u64, density: f64) -> u32 {
+        self.randomize(seed, density);
+        self.population()
+    }
+
+    /// Builds a universe sized to fit an RLE pattern, e.g. one copied from
+    /// the LifeWiki.
+    pub fn from_rle(rle: &str) -> Result<Universe, JsValue> {
+        let pattern = rle::parse(rle).map_err(|e| JsValue::from_str(&e))?;
+        let rule = match &pattern.rule {
+            Some(rule) => Ruleset::parse(rule).map_err(|e| JsValue::from_str(&e))?,
+            None => Ruleset::default(),
+        };
+        let mut universe = Universe::from_cells(pattern.width, pattern.height, pattern.cells);
+        universe.rule = rule;
+        Ok(universe)
+    }
+
+    /// Builds a universe from the plaintext `.cells` format, padding short
+    /// rows with dead cells.
+    pub fn from_plaintext(text: &str) -> Result<Universe, JsValue> {
+        let pattern = plaintext::parse(text).map_err(|e| JsValue::from_str(&e))?;
+        validate_dimensions(pattern.width, pattern.height).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Universe::from_cells(
+            pattern.width,
+            pattern.height,
+            pattern.cells,
+        ))
+    }
+
+    /// Builds a `width`×`height` universe from a Life 1.06 file, shifting
+    /// the pattern so its top-left-most cells sit at row and column 0. Cells
+    /// that still fall off the board are dropped when `clip` is set and are
+    /// an error otherwise.
+    pub fn from_life106(
+        text: &str,
+        width: u32,
+        height: u32,
+        clip: bool,
+    ) -> Result<Universe, JsValue> {
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        let coords = life106::parse(text).map_err(|e| JsValue::from_str(&e))?;
+        let mut universe = Universe::dead(width, height);
+        for (row, col) in coords {
+            if row < height as u64 && col < width as u64 {
+                let idx = universe.get_index(row as u32, col as u32);
+                universe.cells[idx] = Cell::Alive;
+            } else if !clip {
+                return Err(JsValue::from_str(&format!(
+                    "Life 1.06 pattern does not fit a {}x{} board",
+                    width, height
+                )));
+            }
+        }
+        Ok(universe)
+    }
+
+    /// Builds a universe from rows of `0` and `1`, such as `"010\n111\n000"`.
+    /// Every row must be the same length.
+    pub fn from_matrix(text: &str) -> Result<Universe, JsValue> {
+        let pattern = matrix::parse(text).map_err(|e| JsValue::from_str(&e))?;
+        validate_dimensions(pattern.width, pattern.height).map_err(|e| JsValue::from_str(&e))?;
+        Ok(Universe::from_cells(
+            pattern.width,
+            pattern.height,
+            pattern.cells,
+        ))
+    }
+
+    /// Builds an all-dead `width`×`height` board and sets the cells given
+    /// as flat `[row0, col0, row1, col1, ...]` pairs alive.
+    pub fn from_coords(width: u32, height: u32, coords: &[u32]) -> Result<Universe, JsValue> {
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        if !coords.len().is_multiple_of(2) {
+            return Err(JsValue::from_str(
+                "from_coords expects row/column pairs, got an odd number of values",
+            ));
+        }
+        let mut universe = Universe::dead(width, height);
+        for pair in coords.chunks_exact(2) {
+            let (row, col) = (pair[0], pair[1]);
+            if row >= height || col >= width {
+                return Err(JsValue::from_str(&format!(
+                    "cell ({}, {}) is outside the {}x{} board",
+                    row, col, width, height
+                )));
+            }
+            let idx = universe.get_index(row, col);
+            universe.cells[idx] = Cell::Alive;
+        }
+        Ok(universe)
+    }
+
+    /// Builds a board from one row-major byte per cell, `0` dead, `1` alive
+    /// and `2` alive as species B, e.g. a `Uint8Array` produced in JS.
+    pub fn from_bytes(width: u32, height: u32, bytes: &[u8]) -> Result<Universe, JsValue> {
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        let mut universe = Universe::dead(width, height);
+        if bytes.len() != universe.cells.len() {
+            return Err(JsValue::from_str(&format!(
+                "expected {} bytes for a {}x{} board, got {}",
+                universe.cells.len(),
+                width,
+                height,
+                bytes.len()
+            )));
+        }
+        for (cell, &byte) in universe.cells.iter_mut().zip(bytes) {
+            *cell = match byte {
+                0 => Cell::Dead,
+                1 => Cell::Alive,
+                2 => Cell::AliveB,
+                other => {
+                    return Err(JsValue::from_str(&format!(
+                        "invalid cell byte {}, expected 0, 1 or 2",
+                        other
+                    )))
                 }
-            })
-            .collect();
+            };
+        }
+        Ok(universe)
+    }
 
-        Universe {
-            width,
-            height,
-            cells,
+    /// A row-major copy of the cells as `0`/`1`/`2` bytes, the inverse of
+    /// `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.row_major_cells()
+            .iter()
+            .map(|&cell| cell as u8)
+            .collect()
+    }
+
+    /// Writes the board as rows of `0` and `1`, the inverse of `from_matrix`.
+    /// Both species are written as `1`, so they read back as `Alive`.
+    pub fn to_matrix(&self) -> String {
+        self.render_with('1', '0')
+    }
+
+    /// Serializes the board as canonical RLE, the inverse of `from_rle`.
+    /// Species B is kept, using multi-state RLE letters.
+    pub fn to_rle(&self) -> String {
+        rle::encode(self.width, self.height, &self.row_major_cells(), &self.rule)
+    }
+
+    /// Whether the cell at `(row, col)` is alive. Coordinates outside the
+    /// board read as dead.
+    pub fn get_cell(&self, row: u32, col: u32) -> bool {
+        self.try_index(row, col)
+            .is_some_and(|idx| self.cells[idx].is_alive())
+    }
+
+    /// Stamps a `width`×`height` row-major pattern (non-zero bytes alive) with
+    /// its top-left corner at `(top, left)`. Parts that run past an edge wrap
+    /// around on a toroidal board and are clipped on a fixed one.
+    pub fn insert_pattern(&mut self, top: u32, left: u32, width: u32, height: u32, alive: &[u8]) {
+        self.stamp(top, left, width, height, |idx| {
+            alive.get(idx).map(|&state| Cell::from(state != 0))
+        });
+    }
+
+    /// Parses an RLE pattern and stamps its bounding box with the top-left
+    /// corner at `(top, left)`, like `insert_pattern`: dead cells in the
+    /// pattern overwrite the board too, and edges wrap or clip according to
+    /// the boundary mode. Species B cells from a multistate pattern stay
+    /// species B. Any rule in the header is ignored.
+    pub fn stamp_rle(&mut self, rle: &str, top: u32, left: u32) -> Result<(), JsValue> {
+        let pattern = rle::parse(rle).map_err(|e| JsValue::from_str(&e))?;
+        self.stamp(top, left, pattern.width, pattern.height, |idx| {
+            pattern.cells.get(idx).copied()
+        });
+        Ok(())
+    }
+
+    /// Reads a `height`×`width` region starting at `(top, left)` as a flat
+    /// row-major array of 0/1 bytes. The region wraps around on a toroidal
+    /// board; parts past a fixed edge read as dead.
+    pub fn copy_region(&self, top: u32, left: u32, height: u32, width: u32) -> Vec<u8> {
+        let mut region = Vec::with_capacity(width as usize * height as usize);
+        for row in 0..height {
+            for col in 0..width {
+                let source =
+                    self.boundary_coords(top as i64 + row as i64, left as i64 + col as i64);
+                let alive = match source {
+                    Some((r, c)) => self.cells[self.get_index(r, c)].is_alive(),
+                    None => false,
+                };
+                region.push(alive as u8);
+            }
+        }
+        region
+    }
+
+    /// Writes a region read with `copy_region` back with its top-left corner
+    /// at `(top, left)`, wrapping or clipping like `insert_pattern`.
+    pub fn paste_region(&mut self, top: u32, left: u32, height: u32, width: u32, cells: &[u8]) {
+        self.insert_pattern(top, left, width, height, cells);
+    }
+
+    /// Places a glider heading down and to the right, with its 3×3 bounding
+    /// box starting at `(row, col)`.
+    pub fn insert_glider(&mut self, row: u32, col: u32) {
+        self.insert_rows(row, col, &[".O.", "..O", "OOO"]);
+    }
+
+    /// Places a horizontal period-2 blinker starting at `(row, col)`.
+    pub fn insert_blinker(&mut self, row: u32, col: u32) {
+        self.insert_rows(row, col, &["OOO"]);
+    }
+
+    /// Places a period-3 pulsar with its 13×13 bounding box starting at
+    /// `(row, col)`.
+    pub fn insert_pulsar(&mut self, row: u32, col: u32) {
+        self.insert_rows(
+            row,
+            col,
+            &[
+                "..OOO...OOO..",
+                ".............",
+                "O....O.O....O",
+                "O....O.O....O",
+                "O....O.O....O",
+                "..OOO...OOO..",
+                ".............",
+                "..OOO...OOO..",
+                "O....O.O....O",
+                "O....O.O....O",
+                "O....O.O....O",
+                ".............",
+                "..OOO...OOO..",
+            ],
+        );
+    }
+
+    /// How many live neighbors the cell at `(row, col)` has under the current
+    /// boundary mode, counting a cell once per offset that reaches it on
+    /// small wrapping boards (see `BoundaryMode`). Out-of-range coordinates
+    /// have none.
+    pub fn neighbors_alive(&self, row: u32, col: u32) -> u32 {
+        if row < self.height && col < self.width {
+            self.live_neighbor_count(row, col)
+        } else {
+            0
+        }
+    }
+
+    /// Flips the cell at `(row, col)`. Coordinates off the board are
+    /// ignored rather than trapping the module.
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        if let Some(idx) = self.try_index(row, col) {
+            self.record_history();
+            let mut cell = self.cells[idx];
+            cell.toggle();
+            self.write_cell(idx, cell);
+        }
+    }
+
+    /// Makes the cell at `(row, col)` alive or dead regardless of its
+    /// current state. Coordinates off the board are ignored.
+    pub fn set_cell(&mut self, row: u32, col: u32, alive: bool) {
+        if let Some(idx) = self.try_index(row, col) {
+            self.record_history();
+            if self.cells[idx].is_alive() != alive {
+                self.write_cell(idx, Cell::from(alive));
+            }
+        }
+    }
+
+    /// Sets the cell at `(row, col)` to exactly `cell`, e.g. `AliveB` to seed
+    /// the second species. Coordinates off the board are ignored.
+    pub fn set_species(&mut self, row: u32, col: u32, cell: Cell) {
+        if let Some(idx) = self.try_index(row, col) {
+            self.record_history();
+            self.write_cell(idx, cell);
         }
     }
 
-    pub fn toggle_cell(&mut self, row: u32, col: u32){
-        let idx = self.get_index(row, col);
-        self.cells[idx].toggle();
+    /// Coordinates of every alive cell as flat `[row0, col0, row1, col1, ...]`
+    /// pairs in row-major order; much smaller than `cells` on a sparse board.
+    pub fn live_coords(&self) -> Vec<u32> {
+        let mut coords = Vec::new();
+        for (row, col) in self.live_cells() {
+            coords.push(row);
+            coords.push(col);
+        }
+        coords
+    }
+
+    /// Toggles a batch of cells given as flat `[row0, col0, row1, col1, ...]`
+    /// pairs in one call, e.g. for a freehand stroke. Coordinates off the
+    /// board are skipped; the whole batch is undone as one edit.
+    pub fn toggle_cells(&mut self, coords: &[u32]) -> Result<(), JsValue> {
+        if !coords.len().is_multiple_of(2) {
+            return Err(JsValue::from_str(
+                "toggle_cells expects row/column pairs, got an odd number of values",
+            ));
+        }
+        self.record_history();
+        for pair in coords.chunks_exact(2) {
+            if let Some(idx) = self.try_index(pair[0], pair[1]) {
+                let mut cell = self.cells[idx];
+                cell.toggle();
+                self.write_cell(idx, cell);
+            }
+        }
+        Ok(())
+    }
+
+    /// Pointer to the per-cell ages: how many consecutive generations each
+    /// cell has stayed alive, `0` for newborn and dead cells.
+    pub fn ages(&self) -> *const u32 {
+        self.ages.as_ptr()
+    }
+
+    pub fn get_age(&self, row: u32, col: u32) -> u32 {
+        self.try_index(row, col).map_or(0, |idx| self.ages[idx])
     }
 
     pub fn render(&self) -> String {
         self.to_string()
     }
+
+    /// Like `render`, but drawing cells with the given characters, e.g.
+    /// `'#'` and `'.'` for terminals that don't show `◼`/`◻` well.
+    pub fn render_with(&self, alive: char, dead: char) -> String {
+        let mut out = String::new();
+        self.write_grid(&mut out, alive, dead)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Every maximal horizontal run of alive cells as flat
+    /// `[row, start_col, length, ...]` triples, so a canvas renderer can fill
+    /// one rectangle per run instead of one per cell.
+    pub fn alive_row_spans(&self) -> Vec<u32> {
+        let mut spans = Vec::new();
+        for row in 0..self.height {
+            let mut start = None;
+            for col in 0..=self.width {
+                let alive = col < self.width && self.cells[self.get_index(row, col)].is_alive();
+                match (alive, start) {
+                    (true, None) => start = Some(col),
+                    (false, Some(first)) => {
+                        spans.extend_from_slice(&[row, first, col - first]);
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        spans
+    }
+
+    /// Renders one pixel per cell as RGBA bytes, ready to wrap in an
+    /// `ImageData`. Colors are packed as `0xRRGGBBAA`.
+    pub fn render_rgba(&self, alive: u32, dead: u32) -> Vec<u8> {
+        let alive = alive.to_be_bytes();
+        let dead = dead.to_be_bytes();
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for &cell in self.row_major_cells().iter() {
+            let color = if cell.is_alive() { &alive } else { &dead };
+            pixels.extend_from_slice(color);
+        }
+        pixels
+    }
+
+    /// Like `render_rgba`, but colors the two species apart: `alive` for
+    /// species A and `alive_b` for species B.
+    pub fn render_rgba_species(&self, alive: u32, alive_b: u32, dead: u32) -> Vec<u8> {
+        let colors = [
+            dead.to_be_bytes(),
+            alive.to_be_bytes(),
+            alive_b.to_be_bytes(),
+        ];
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for &cell in self.row_major_cells().iter() {
+            pixels.extend_from_slice(&colors[cell as usize]);
+        }
+        pixels
+    }
+
+    /// Renders a Generations board one pixel per cell, fading dying cells:
+    /// alive cells get `gradient[0]`, a cell `k` generations into dying gets
+    /// `gradient[k]`, and dead cells get the last entry. `gradient` needs at
+    /// least `num_states()` entries.
+    pub fn render_rgba_generations(&self, gradient: &[u32]) -> Result<Vec<u8>, JsValue> {
+        if gradient.len() < self.num_states as usize {
+            return Err(JsValue::from_str(&format!(
+                "gradient has {} colors, expected at least {} for {} states",
+                gradient.len(),
+                self.num_states,
+                self.num_states
+            )));
+        }
+        let dead = gradient[gradient.len() - 1];
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let color = if self.cells[idx].is_alive() {
+                    gradient[0]
+                } else if self.num_states > 2 {
+                    match self.states.get(idx) {
+                        Some(&state) if state >= 2 => gradient[state as usize - 1],
+                        _ => dead,
+                    }
+                } else {
+                    dead
+                };
+                pixels.extend_from_slice(&color.to_be_bytes());
+            }
+        }
+        Ok(pixels)
+    }
+
+    /// Renders the board into an `out_w`×`out_h` RGBA image for a minimap,
+    /// where each pixel blends `alive` and `dead` by the alive fraction of
+    /// the cells it covers. Pixels whose edges fall partway through a cell
+    /// weigh that cell by the part they cover, so any output size works.
+    pub fn render_rgba_downscaled(&self, out_w: u32, out_h: u32, alive: u32, dead: u32) -> Vec<u8> {
+        // For each output pixel along an axis, the source cells it overlaps
+        // and what share of the pixel each one covers.
+        let coverage = |len: u32, out: u32| -> Vec<Vec<(u32, f64)>> {
+            let scale = len as f64 / out as f64;
+            (0..out)
+                .map(|i| {
+                    let (start, end) = (i as f64 * scale, (i + 1) as f64 * scale);
+                    (start.floor() as u32..(end.ceil() as u32).min(len))
+                        .map(|cell| {
+                            let overlap = end.min(cell as f64 + 1.0) - start.max(cell as f64);
+                            (cell, overlap / scale)
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        let rows = coverage(self.height, out_h);
+        let cols = coverage(self.width, out_w);
+
+        let alive = alive.to_be_bytes();
+        let dead = dead.to_be_bytes();
+        let mut pixels = Vec::with_capacity(out_w as usize * out_h as usize * 4);
+        for row_cover in &rows {
+            for col_cover in &cols {
+                let mut fraction = 0.0;
+                for &(row, row_weight) in row_cover {
+                    for &(col, col_weight) in col_cover {
+                        if self.cells[self.get_index(row, col)].is_alive() {
+                            fraction += row_weight * col_weight;
+                        }
+                    }
+                }
+                for (&a, &d) in alive.iter().zip(dead.iter()) {
+                    let channel = d as f64 + (a as f64 - d as f64) * fraction;
+                    pixels.push(channel.round().clamp(0.0, 255.0) as u8);
+                }
+            }
+        }
+        pixels
+    }
 }
 
+/// An all-dead board the same size as `Universe::new()`.
+impl Default for Universe {
+    fn default() -> Self {
+        Universe::dead(128, 128)
+    }
+}
+
+/// Two universes are equal when they have the same dimensions and cells.
+/// Rule, generation, layout and other settings are not compared.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Universe) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && if self.layout == other.layout {
+                self.cells == other.cells
+            } else {
+                self.row_major_cells() == other.row_major_cells()
+            }
+    }
+}
+
+impl Eq for Universe {}
+
 impl Display for Universe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for line in self.cells.as_slice().chunks(self.width as usize) {
-            for &cell in line {
-                let symbol = if cell == Cell::Alive { '◼' } else { '◻' };
-                write!(f, "{}", symbol)?;
-            }
-            write!(f, "\n")?;
-        }
-        Ok(())
+        self.write_grid(f, '◼', '◻')
+    }
+}
+
+/// Parses the `◼`/`◻` grid that `Display` prints, so boards round-trip
+/// through `to_string()` and `parse()`. Every row must be the same length.
+impl FromStr for Universe {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Universe, String> {
+        let pattern = matrix::parse_with(text, '◼', '◻')?;
+        validate_dimensions(pattern.width, pattern.height)?;
+        Ok(Universe::from_cells(
+            pattern.width,
+            pattern.height,
+            pattern.cells,
+        ))
     }
 }
 
+/// Logs how long its scope took with `console.time`/`console.timeEnd`, and
+/// lets callers read the elapsed time themselves via `elapsed_ms`.
+///
+/// Off the browser there is no console to log to, so on non-wasm targets
+/// nothing is logged and the elapsed time comes from `std::time::Instant`.
+#[cfg(target_arch = "wasm32")]
 pub struct Timer<'a> {
-    name: &'a str
+    name: Option<&'a str>,
+    start: f64,
 }
 
+#[cfg(target_arch = "wasm32")]
 impl<'a> Timer<'a> {
-    pub fn new(name: &'a str) -> Timer<'a>{
+    pub fn new(name: &'a str) -> Timer<'a> {
         console::time_with_label(name);
-        Timer { name }
+        Timer {
+            name: Some(name),
+            start: Timer::now(),
+        }
+    }
+
+    /// A timer that only measures, without logging to the console.
+    pub fn silent() -> Timer<'a> {
+        Timer {
+            name: None,
+            start: Timer::now(),
+        }
+    }
+
+    /// Milliseconds since the timer was created.
+    pub fn elapsed_ms(&self) -> f64 {
+        Timer::now() - self.start
+    }
+
+    fn now() -> f64 {
+        web_sys::window()
+            .and_then(|window| window.performance())
+            .map(|performance| performance.now())
+            .unwrap_or(0.0)
     }
 }
 
+#[cfg(target_arch = "wasm32")]
 impl<'a> Drop for Timer<'a> {
     fn drop(&mut self) {
-        console::time_end_with_label(self.name);
+        if let Some(name) = self.name {
+            console::time_end_with_label(name);
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Timer<'a> {
+    name: PhantomData<&'a str>,
+    start: Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> Timer<'a> {
+    pub fn new(_name: &'a str) -> Timer<'a> {
+        Timer::silent()
+    }
+
+    pub fn silent() -> Timer<'a> {
+        Timer {
+            name: PhantomData,
+            start: Instant::now(),
+        }
+    }
+
+    /// Milliseconds since the timer was created.
+    pub fn elapsed_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> Drop for Timer<'a> {
+    fn drop(&mut self) {}
+}
@@ -0,0 +1,44 @@
+//! Reading patterns in the Life 1.06 format: a `#Life 1.06` header followed
+//! by one `x y` coordinate pair per alive cell, where coordinates may be
+//! negative.
+//!
+//! See https://conwaylife.com/wiki/Life_1.06
+
+/// Parses the alive cells as `(row, col)` pairs, translated so the smallest
+/// `y` becomes row 0 and the smallest `x` becomes column 0.
+pub fn parse(text: &str) -> Result<Vec<(u64, u64)>, String> {
+    let mut coords = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let mut coordinate = |name| {
+            fields
+                .next()
+                .and_then(|field| field.parse::<i64>().ok())
+                .ok_or_else(|| {
+                    format!("line {} is missing a valid {} coordinate", number + 1, name)
+                })
+        };
+        let x = coordinate("x")?;
+        let y = coordinate("y")?;
+        if fields.next().is_some() {
+            return Err(format!("line {} has more than two coordinates", number + 1));
+        }
+        coords.push((y, x));
+    }
+
+    let min_row = coords.iter().map(|&(row, _)| row).min().unwrap_or(0);
+    let min_col = coords.iter().map(|&(_, col)| col).min().unwrap_or(0);
+    Ok(coords
+        .into_iter()
+        .map(|(row, col)| {
+            (
+                (row as i128 - min_row as i128) as u64,
+                (col as i128 - min_col as i128) as u64,
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,58 @@
+//! Reading boards written as rows of `0` (dead) and `1` (alive), the
+//! easiest format to type out by hand for small fixtures, or of any other
+//! pair of characters such as the `◼`/`◻` that `Display` prints.
+
+use crate::rle::Pattern;
+use crate::Cell;
+
+pub fn parse(text: &str) -> Result<Pattern, String> {
+    parse_with(text, '1', '0')
+}
+
+pub fn parse_with(text: &str, alive: char, dead: char) -> Result<Pattern, String> {
+    let mut width = None;
+    let mut height = 0;
+    let mut cells = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        for ch in line.chars() {
+            match ch {
+                _ if ch == dead => cells.push(Cell::Dead),
+                _ if ch == alive => cells.push(Cell::Alive),
+                other => {
+                    return Err(format!(
+                        "unexpected character {:?} on line {}",
+                        other,
+                        number + 1
+                    ))
+                }
+            }
+        }
+        let len = line.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(width) if width != len => {
+                return Err(format!(
+                    "line {} has {} cells, expected {}",
+                    number + 1,
+                    len,
+                    width
+                ))
+            }
+            Some(_) => {}
+        }
+        height += 1;
+    }
+
+    let width = width.unwrap_or(0);
+    if width == 0 {
+        return Err("matrix has no cells".to_string());
+    }
+
+    Ok(Pattern {
+        width: width as u32,
+        height,
+        cells,
+        rule: None,
+    })
+}
@@ -0,0 +1,83 @@
+//! Random cell flips after each generation, for studying how patterns hold
+//! up to mutation.
+
+use wasm_bindgen::prelude::*;
+
+use crate::rng::XorShift64;
+use crate::Universe;
+
+#[derive(Clone, Debug)]
+pub struct Noise {
+    rng: XorShift64,
+    flip_probability: f64,
+}
+
+impl Universe {
+    /// Flips each cell with the configured probability, folding the flips
+    /// into `changed` so it still lists exactly the cells that differ from
+    /// the previous generation. In Generations mode a flipped cell becomes
+    /// plainly alive or fully dead, skipping the dying states.
+    pub(crate) fn apply_noise(&mut self) {
+        let noise = match self.noise.as_mut() {
+            Some(noise) => noise,
+            None => return,
+        };
+
+        let mut flipped = Vec::new();
+        for (idx, cell) in self.cells.iter_mut().enumerate() {
+            // Draw for frozen cells too, so freezing doesn't shift the
+            // sequence the rest of the board sees.
+            if noise.rng.next_f64() < noise.flip_probability
+                && !self.frozen.get(idx).copied().unwrap_or(false)
+            {
+                cell.toggle();
+                self.ages[idx] = 0;
+                if self.num_states > 2 {
+                    self.states[idx] = cell.is_alive() as u8;
+                }
+                flipped.push(idx as u32);
+            }
+        }
+        if flipped.is_empty() {
+            return;
+        }
+
+        // Both lists are sorted; a cell in both flipped twice and is back to
+        // where it started.
+        let mut changed = Vec::with_capacity(self.changed.len() + flipped.len());
+        let mut stepped = self.changed.iter().copied().peekable();
+        let mut flipped = flipped.into_iter().peekable();
+        loop {
+            match (stepped.peek().copied(), flipped.peek().copied()) {
+                (Some(x), Some(y)) if x == y => {
+                    stepped.next();
+                    flipped.next();
+                }
+                (Some(x), Some(y)) if x < y => changed.extend(stepped.next()),
+                (_, Some(_)) => changed.extend(flipped.next()),
+                (Some(_), None) => changed.extend(stepped.next()),
+                (None, None) => break,
+            }
+        }
+        self.changed = changed;
+        self.invalidate_caches();
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Flips each cell with probability `flip_probability` at the end of
+    /// every generation, making runs stochastic. The flips come from a
+    /// generator seeded with `seed`, so the same seed and board always give
+    /// the same run. A probability of `0` (the default) turns noise off.
+    pub fn set_noise(&mut self, seed: u64, flip_probability: f64) {
+        self.noise = if flip_probability > 0.0 {
+            Some(Noise {
+                rng: XorShift64::new(seed),
+                flip_probability: flip_probability.min(1.0),
+            })
+        } else {
+            None
+        };
+    }
+}
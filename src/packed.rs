@@ -0,0 +1,216 @@
+//! A bit-packed alternative to `Universe` for memory-constrained hosts.
+//!
+//! Cells are stored one bit each in `u64` words, so a 1024×1024 board takes
+//! 128KiB instead of a megabyte. There is no raw cell pointer; read cells with
+//! `get_cell` instead.
+//!
+//! This is a separate type rather than a feature that swaps the storage
+//! under `Universe`, because much of `Universe` needs more than one bit per
+//! cell: the second species, ages, Generations states, frozen cells and the
+//! writable `cells_mut_ptr` view all assume a byte per cell. A packed
+//! `Universe` would have had to drop them under a feature flag, so the
+//! packed board covers two-state Moore rules only and is built from a
+//! `Universe` with `from_universe`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{validate_dimensions, BoundaryMode, Neighborhood, Ruleset, Timer, Universe};
+
+const WORD_BITS: usize = 64;
+
+#[wasm_bindgen]
+pub struct PackedUniverse {
+    width: u32,
+    height: u32,
+    words: Vec<u64>,
+    next_words: Vec<u64>,
+    generation: u64,
+    boundary: BoundaryMode,
+    twist: i32,
+    rule: Ruleset,
+}
+
+impl PackedUniverse {
+    fn dead(width: u32, height: u32) -> PackedUniverse {
+        let words = vec![0; (width as usize * height as usize).div_ceil(WORD_BITS)];
+        PackedUniverse {
+            width,
+            height,
+            next_words: words.clone(),
+            words,
+            generation: 0,
+            boundary: BoundaryMode::Toroidal,
+            twist: 0,
+            rule: Ruleset::default(),
+        }
+    }
+
+    /// Why `universe` can't be packed, if it uses settings that
+    /// `PackedUniverse::tick` doesn't simulate.
+    fn unsupported(universe: &Universe) -> Option<&'static str> {
+        if universe.radius != 1 || universe.neighborhood != Neighborhood::Moore {
+            Some("only the radius-1 Moore neighborhood can be packed")
+        } else if universe.num_states != 2 {
+            Some("Generations rules can't be packed")
+        } else if universe.frozen.contains(&true) {
+            Some("boards with frozen cells can't be packed")
+        } else if universe.noise.is_some() {
+            Some("boards with noise can't be packed")
+        } else {
+            None
+        }
+    }
+
+    fn get_index(&self, row: u32, column: u32) -> (usize, u64) {
+        let idx = row as usize * self.width as usize + column as usize;
+        (idx / WORD_BITS, 1 << (idx % WORD_BITS))
+    }
+
+    fn is_alive(&self, row: u32, column: u32) -> bool {
+        let (word, mask) = self.get_index(row, column);
+        self.words[word] & mask != 0
+    }
+
+    fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let mut count: u8 = 0;
+        for d_r in -1..=1 {
+            for d_c in -1..=1 {
+                if d_r == 0 && d_c == 0 {
+                    continue;
+                }
+                let neighbor = self.boundary.resolve(
+                    row as i64 + d_r,
+                    column as i64 + d_c,
+                    self.height as i64,
+                    self.width as i64,
+                    self.twist,
+                );
+                if let Some((n_r, n_c)) = neighbor {
+                    count += self.is_alive(n_r as u32, n_c as u32) as u8;
+                }
+            }
+        }
+        count
+    }
+}
+
+#[wasm_bindgen]
+impl PackedUniverse {
+    /// Creates an all-dead board of the given size, which must be non-zero
+    /// and fit in memory.
+    pub fn new(width: u32, height: u32) -> Result<PackedUniverse, JsValue> {
+        validate_dimensions(width, height).map_err(|e| JsValue::from_str(&e))?;
+        Ok(PackedUniverse::dead(width, height))
+    }
+
+    /// Packs the current state of a byte-per-cell `Universe`, along with its
+    /// rule and boundary mode. Both species pack as plain alive cells. Fails
+    /// for settings the packed tick doesn't simulate: a larger or von
+    /// Neumann neighborhood, Generations states, frozen cells or noise.
+    pub fn from_universe(universe: &Universe) -> Result<PackedUniverse, JsValue> {
+        if let Some(reason) = PackedUniverse::unsupported(universe) {
+            return Err(JsValue::from_str(reason));
+        }
+        let mut packed = PackedUniverse::dead(universe.width, universe.height);
+        for (idx, &cell) in universe.row_major_cells().iter().enumerate() {
+            if cell.is_alive() {
+                packed.words[idx / WORD_BITS] |= 1 << (idx % WORD_BITS);
+            }
+        }
+        packed.generation = universe.generation;
+        packed.boundary = universe.boundary;
+        packed.twist = universe.twist;
+        packed.rule = universe.rule;
+        Ok(packed)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn set_boundary_mode(&mut self, mode: BoundaryMode) {
+        self.boundary = mode;
+    }
+
+    /// See `Universe::set_rule`.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), JsValue> {
+        self.rule = Ruleset::parse(rule).map_err(|e| JsValue::from_str(&e))?;
+        Ok(())
+    }
+
+    /// See `Universe::set_twist`.
+    pub fn set_twist(&mut self, twist: i32) {
+        self.twist = twist;
+    }
+
+    pub fn get_cell(&self, row: u32, col: u32) -> bool {
+        row < self.height && col < self.width && self.is_alive(row, col)
+    }
+
+    pub fn set_cell(&mut self, row: u32, col: u32, alive: bool) {
+        if row >= self.height || col >= self.width {
+            return;
+        }
+        let (word, mask) = self.get_index(row, col);
+        if alive {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        if row >= self.height || col >= self.width {
+            return;
+        }
+        let (word, mask) = self.get_index(row, col);
+        self.words[word] ^= mask;
+    }
+
+    pub fn population(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    pub fn clear(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = 0;
+        }
+        self.generation = 0;
+    }
+
+    pub fn tick(&mut self) {
+        let _timer = Timer::new("packed_universe_tick");
+        let mut next_words = std::mem::take(&mut self.next_words);
+        for word in next_words.iter_mut() {
+            *word = 0;
+        }
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let alive = self.is_alive(row, column);
+                let live_neighbors = self.live_neighbor_count(row, column) as usize;
+                let rule = if alive {
+                    &self.rule.survive
+                } else {
+                    &self.rule.birth
+                };
+                if rule[live_neighbors] {
+                    let (word, mask) = self.get_index(row, column);
+                    next_words[word] |= mask;
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.words, &mut next_words);
+        self.next_words = next_words;
+        self.generation += 1;
+    }
+}
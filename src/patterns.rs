@@ -0,0 +1,43 @@
+//! Built-in starter patterns, stored as RLE and stamped by name.
+
+use wasm_bindgen::prelude::*;
+
+use crate::Universe;
+
+/// `(name, rle)` for every pattern `insert_named` knows.
+const PATTERNS: &[(&str, &str)] = &[
+    ("glider", "x = 3, y = 3\nbo$2bo$3o!"),
+    ("lwss", "x = 5, y = 4\nbo2bo$o4b$o3bo$4o!"),
+    (
+        "pulsar",
+        "x = 13, y = 13\n2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$\
+         o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    ),
+    (
+        "gosper_glider_gun",
+        "x = 36, y = 9\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$\
+         2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!",
+    ),
+];
+
+#[wasm_bindgen]
+impl Universe {
+    /// Stamps a built-in pattern with its top-left corner at `(row, col)`,
+    /// like `stamp_rle`. Known names are `glider`, `lwss`, `pulsar` and
+    /// `gosper_glider_gun`; any other name is an error listing them.
+    pub fn insert_named(&mut self, name: &str, row: u32, col: u32) -> Result<(), JsValue> {
+        let text = PATTERNS
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, text)| *text)
+            .ok_or_else(|| {
+                let names: Vec<&str> = PATTERNS.iter().map(|(known, _)| *known).collect();
+                JsValue::from_str(&format!(
+                    "unknown pattern {:?}, expected one of: {}",
+                    name,
+                    names.join(", ")
+                ))
+            })?;
+        self.stamp_rle(text, row, col)
+    }
+}
@@ -0,0 +1,49 @@
+//! Reading patterns in the plaintext `.cells` format: `.` for dead, `O` for
+//! alive, and `!` at the start of a line for comments.
+//!
+//! See https://conwaylife.com/wiki/Plaintext
+
+use crate::rle::Pattern;
+use crate::Cell;
+
+pub fn parse(text: &str) -> Result<Pattern, String> {
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.starts_with('!') {
+            continue;
+        }
+        let row = line
+            .trim_end()
+            .chars()
+            .map(|ch| match ch {
+                '.' => Ok(Cell::Dead),
+                'O' | '*' => Ok(Cell::Alive),
+                other => Err(format!(
+                    "unexpected character {:?} on line {}",
+                    other,
+                    number + 1
+                )),
+            })
+            .collect::<Result<Vec<Cell>, String>>()?;
+        rows.push(row);
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if width == 0 {
+        return Err("plaintext pattern has no cells".to_string());
+    }
+
+    let height = rows.len();
+    let mut cells = Vec::with_capacity(width * height);
+    for mut row in rows {
+        row.resize(width, Cell::Dead);
+        cells.extend(row);
+    }
+
+    Ok(Pattern {
+        width: width as u32,
+        height: height as u32,
+        cells,
+        rule: None,
+    })
+}
@@ -0,0 +1,98 @@
+//! A rolling record of the population after each generation, for plotting.
+
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::Universe;
+
+/// How many generations of population are kept unless configured otherwise.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+#[derive(Debug)]
+pub struct PopulationHistory {
+    values: VecDeque<u32>,
+    capacity: usize,
+}
+
+impl PopulationHistory {
+    pub fn new(capacity: usize) -> PopulationHistory {
+        PopulationHistory {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.values.len() > capacity {
+            self.values.pop_front();
+        }
+    }
+
+    /// Appends a value, dropping the oldest once at capacity.
+    pub fn push(&mut self, population: u32) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(population);
+    }
+}
+
+impl Default for PopulationHistory {
+    fn default() -> PopulationHistory {
+        PopulationHistory::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl Universe {
+    /// Appends the current population and tallies the generation's births
+    /// and deaths from `changed`; called once per generation ticked.
+    pub(crate) fn record_population(&mut self) {
+        let births = self
+            .changed
+            .iter()
+            .filter(|&&idx| self.cells[idx as usize].is_alive())
+            .count() as u32;
+        self.births = births;
+        self.deaths = self.changed.len() as u32 - births;
+        if self.populations.capacity() > 0 {
+            let population = self.population();
+            self.populations.push(population);
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// The population after each of the most recent generations, oldest
+    /// first, up to the capacity set with `set_history_capacity`.
+    pub fn population_history(&self) -> Vec<u32> {
+        self.populations.values.iter().copied().collect()
+    }
+
+    /// How many cells came alive in the most recent generation ticked.
+    pub fn last_births(&self) -> u32 {
+        self.births
+    }
+
+    /// How many cells died in the most recent generation ticked. A board
+    /// with births and deaths both high but balanced is churning rather than
+    /// static.
+    pub fn last_deaths(&self) -> u32 {
+        self.deaths
+    }
+
+    /// How many generations of population to keep (256 by default); `0`
+    /// stops recording. Unrelated to `set_history_depth`, which is for undo.
+    pub fn set_history_capacity(&mut self, capacity: u32) {
+        self.populations.set_capacity(capacity as usize);
+    }
+}
@@ -0,0 +1,222 @@
+//! Reading and writing patterns in the run-length encoded (RLE) format used by
+//! the LifeWiki and most Life software.
+//!
+//! Boards with two species use the multi-state letters instead of `b`/`o`:
+//! `.` for dead, `A` for `Alive` and `B` for `AliveB`.
+//!
+//! See https://conwaylife.com/wiki/Run_Length_Encoded
+
+use crate::{validate_dimensions, Cell, Ruleset};
+
+/// A decoded RLE pattern, laid out row-major.
+pub struct Pattern {
+    pub width: u32,
+    pub height: u32,
+    pub cells: Vec<Cell>,
+    pub rule: Option<String>,
+}
+
+/// Parses an RLE pattern. Errors in the header or body name the 1-based line
+/// and column of the offending character, counted in the original text.
+pub fn parse(rle: &str) -> Result<Pattern, String> {
+    let mut lines = rle
+        .lines()
+        .enumerate()
+        .map(|(number, line)| (number + 1, line))
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        });
+
+    let (header_line, header) = lines
+        .next()
+        .ok_or_else(|| "RLE input is missing the `x = .., y = ..` header".to_string())?;
+    let (width, height, rule) =
+        parse_header(header.trim()).map_err(|e| format!("{} on line {}", e, header_line))?;
+    validate_dimensions(width, height)?;
+
+    let mut cells = vec![Cell::Dead; width as usize * height as usize];
+    let mut row: u32 = 0;
+    let mut col: u32 = 0;
+    let mut run: Option<u32> = None;
+    let mut terminated = false;
+
+    'body: for (line_number, line) in lines {
+        for (column, ch) in line.chars().enumerate() {
+            let at =
+                |message: String| format!("{} at line {} col {}", message, line_number, column + 1);
+            match ch {
+                '0'..='9' => {
+                    let digit = ch as u32 - '0' as u32;
+                    let count = run
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|count| count.checked_add(digit))
+                        .ok_or_else(|| at("RLE run count is too large".to_string()))?;
+                    run = Some(count);
+                }
+                'b' | 'o' | '.' | 'A' | 'B' => {
+                    let count = take_run(&mut run).map_err(at)?;
+                    if col as u64 + count as u64 > width as u64 || row >= height {
+                        return Err(at(format!(
+                            "RLE row {} is wider than the declared width x = {}",
+                            row, width
+                        )));
+                    }
+                    let tagged = match ch {
+                        'o' | 'A' => Cell::Alive,
+                        'B' => Cell::AliveB,
+                        _ => Cell::Dead,
+                    };
+                    if tagged != Cell::Dead {
+                        let start = row as usize * width as usize + col as usize;
+                        for cell in &mut cells[start..start + count as usize] {
+                            *cell = tagged;
+                        }
+                    }
+                    col += count;
+                }
+                '$' => {
+                    let count = take_run(&mut run).map_err(at)?;
+                    row = row.saturating_add(count);
+                    col = 0;
+                }
+                '!' => {
+                    if run.is_some() {
+                        return Err(at("RLE run count is not followed by a tag".to_string()));
+                    }
+                    terminated = true;
+                    break 'body;
+                }
+                c if c.is_whitespace() => {}
+                c => return Err(at(format!("unexpected character {:?}", c))),
+            }
+        }
+    }
+
+    if !terminated && run.is_some() {
+        return Err("RLE run count is not followed by a tag".to_string());
+    }
+    if row > height || (row == height && col > 0) {
+        return Err(format!(
+            "RLE pattern has more rows than the declared height y = {}",
+            height
+        ));
+    }
+
+    Ok(Pattern {
+        width,
+        height,
+        cells,
+        rule,
+    })
+}
+
+/// Longest body line written by `encode`, as recommended by the format.
+const MAX_LINE_LEN: usize = 70;
+
+/// Encodes a row-major cell buffer as canonical RLE. Trailing dead cells in a
+/// row and trailing empty rows are omitted, and runs of one have no count.
+/// The header records `rule` so the pattern reloads with the same rule.
+pub fn encode(width: u32, height: u32, cells: &[Cell], rule: &Ruleset) -> String {
+    let multistate = cells.contains(&Cell::AliveB);
+    let mut tokens: Vec<String> = Vec::new();
+    let mut last_row = 0;
+
+    for (row, line) in cells.chunks(width as usize).enumerate() {
+        let mut runs: Vec<(u32, Cell)> = Vec::new();
+        for &cell in line {
+            match runs.last_mut() {
+                Some((count, last)) if *last == cell => *count += 1,
+                _ => runs.push((1, cell)),
+            }
+        }
+        if let Some(&(_, Cell::Dead)) = runs.last() {
+            runs.pop();
+        }
+        if runs.is_empty() {
+            continue;
+        }
+
+        // A single `$` run also covers any blank rows since the last one.
+        if row > last_row {
+            tokens.push(run_token((row - last_row) as u32, '$'));
+        }
+        last_row = row;
+
+        for (count, cell) in runs {
+            let tag = match (multistate, cell) {
+                (false, Cell::Dead) => 'b',
+                (false, _) => 'o',
+                (true, Cell::Dead) => '.',
+                (true, Cell::Alive) => 'A',
+                (true, Cell::AliveB) => 'B',
+            };
+            tokens.push(run_token(count, tag));
+        }
+    }
+    tokens.push("!".to_string());
+
+    let mut out = format!("x = {}, y = {}, rule = {}\n", width, height, rule);
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len + token.len() > MAX_LINE_LEN {
+            out.push('\n');
+            line_len = 0;
+        }
+        line_len += token.len();
+        out.push_str(&token);
+    }
+    out.push('\n');
+    out
+}
+
+fn run_token(count: u32, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", count, tag)
+    }
+}
+
+fn take_run(run: &mut Option<u32>) -> Result<u32, String> {
+    match run.take() {
+        Some(0) => Err("RLE run count must be at least 1".to_string()),
+        Some(count) => Ok(count),
+        None => Ok(1),
+    }
+}
+
+fn parse_header(header: &str) -> Result<(u32, u32, Option<String>), String> {
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+
+    for field in header.split(',') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts
+            .next()
+            .ok_or_else(|| format!("malformed RLE header field {:?}", field.trim()))?
+            .trim();
+        match key {
+            "x" => width = Some(parse_dimension("x", value)?),
+            "y" => height = Some(parse_dimension("y", value)?),
+            "rule" => rule = Some(value.to_string()),
+            other => return Err(format!("unknown RLE header field {:?}", other)),
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err("RLE header must declare both x and y".to_string()),
+    }
+}
+
+fn parse_dimension(name: &str, value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(0) => Err(format!("RLE header {} must be greater than zero", name)),
+        Ok(n) => Ok(n),
+        Err(_) => Err(format!("RLE header {} = {:?} is not a number", name, value)),
+    }
+}
@@ -0,0 +1,59 @@
+/// Small xorshift64* generator so boards can be reproduced from a seed
+/// without pulling in a full `rand` dependency.
+#[derive(Clone, Debug)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+/// A seed that differs from run to run, for boards that don't need to be
+/// reproducible. In the browser it comes from `Math.random()`.
+#[cfg(target_arch = "wasm32")]
+pub fn entropy_seed() -> u64 {
+    let high = (js_sys::Math::random() * 4_294_967_296.0) as u64;
+    let low = (js_sys::Math::random() * 4_294_967_296.0) as u64;
+    high << 32 | low
+}
+
+/// A seed that differs from run to run, for boards that don't need to be
+/// reproducible. Natively it mixes std's randomly keyed hasher with the
+/// current time.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn entropy_seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(elapsed.as_nanos());
+    }
+    hasher.finish()
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> XorShift64 {
+        // Run the seed through a splitmix64 step so that small or zero seeds
+        // still give a well-mixed, non-zero starting state.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        XorShift64 {
+            state: if z == 0 { 0x2545_F491_4F6C_DD1D } else { z },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform value in `[0.0, 1.0)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,102 @@
+//! Life-like rules written in B/S notation, e.g. `B3/S23` for Conway's Game
+//! of Life or `B36/S23` for HighLife.
+
+use std::fmt;
+
+/// Which live-neighbor counts cause a dead cell to be born and which let a
+/// live cell survive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ruleset {
+    pub birth: [bool; 9],
+    pub survive: [bool; 9],
+}
+
+impl Ruleset {
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn conway() -> Ruleset {
+        let mut rule = Ruleset {
+            birth: [false; 9],
+            survive: [false; 9],
+        };
+        rule.birth[3] = true;
+        rule.survive[2] = true;
+        rule.survive[3] = true;
+        rule
+    }
+
+    /// Parses a rulestring such as `B3/S23` or `b36/s23`. The older
+    /// survive/birth form without letters (`23/3`) is also accepted.
+    pub fn parse(s: &str) -> Result<Ruleset, String> {
+        let s = s.trim();
+        let mut parts = s.splitn(2, '/');
+        let first = parts.next().unwrap_or("");
+        let second = parts
+            .next()
+            .ok_or_else(|| format!("rule {:?} is missing the '/' separator", s))?;
+
+        let mut birth = None;
+        let mut survive = None;
+        for part in [first, second].iter() {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some('B') | Some('b') if birth.is_none() => {
+                    birth = Some(parse_counts(s, chars.as_str())?)
+                }
+                Some('S') | Some('s') if survive.is_none() => {
+                    survive = Some(parse_counts(s, chars.as_str())?)
+                }
+                _ => {}
+            }
+        }
+
+        match (birth, survive) {
+            (Some(birth), Some(survive)) => Ok(Ruleset { birth, survive }),
+            (None, None) => Ok(Ruleset {
+                survive: parse_counts(s, first)?,
+                birth: parse_counts(s, second)?,
+            }),
+            _ => Err(format!("rule {:?} must have one B part and one S part", s)),
+        }
+    }
+}
+
+/// Canonical `B.../S...` notation with counts in ascending order, the
+/// inverse of `Ruleset::parse`.
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        write_counts(f, &self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, &self.survive)
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Ruleset {
+        Ruleset::conway()
+    }
+}
+
+fn parse_counts(rule: &str, digits: &str) -> Result<[bool; 9], String> {
+    let mut counts = [false; 9];
+    for ch in digits.chars() {
+        match ch.to_digit(10) {
+            Some(n) if n <= 8 => counts[n as usize] = true,
+            _ => {
+                return Err(format!(
+                    "rule {:?} has invalid neighbor count {:?}",
+                    rule, ch
+                ))
+            }
+        }
+    }
+    Ok(counts)
+}
+
+fn write_counts(f: &mut fmt::Formatter<'_>, counts: &[bool; 9]) -> fmt::Result {
+    for (n, _) in counts.iter().enumerate().filter(|&(_, &set)| set) {
+        write!(f, "{}", n)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! An incremental tick that only revisits cells near the previous
+//! generation's changes, for large boards with little activity.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Neighborhood, Universe};
+
+impl Universe {
+    /// Whether the incremental path produces the same generations as `advance`.
+    /// Larger or von Neumann neighborhoods and Generations rules fall back to
+    /// the dense tick.
+    fn sparse_supported(&self) -> bool {
+        self.radius == 1 && self.num_states == 2 && self.neighborhood == Neighborhood::Moore
+    }
+
+    /// Recounts every cell's live neighbors from scratch.
+    fn rebuild_neighbor_counts(&mut self) {
+        let mut counts = std::mem::take(&mut self.neighbor_counts);
+        counts.resize(self.cells.len(), 0);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                counts[self.get_index(row, col)] = self.live_neighbor_count(row, col) as u8;
+            }
+        }
+        self.neighbor_counts = counts;
+        self.counts_valid = true;
+    }
+
+    /// Calls `f` with the index of each of the eight neighbors of `idx`,
+    /// once per time it is counted as a neighbor.
+    fn for_each_neighbor(&self, idx: usize, mut f: impl FnMut(usize)) {
+        let (row, col) = self.coords(idx);
+        let (row, col) = (row as i64, col as i64);
+        for d_r in -1..=1 {
+            for d_c in -1..=1 {
+                if d_r == 0 && d_c == 0 {
+                    continue;
+                }
+                if let Some((n_r, n_c)) = self.boundary_coords(row + d_r, col + d_c) {
+                    f(self.get_index(n_r, n_c));
+                }
+            }
+        }
+    }
+
+    fn advance_sparse(&mut self) {
+        if !self.sparse_supported() {
+            self.advance();
+            return;
+        }
+
+        // Only cells that flipped last generation, and their neighbors, can
+        // have a different count or state than they did then. After edits or
+        // a dense tick the counts are rebuilt and every cell is a candidate.
+        let mut candidates = Vec::new();
+        if self.counts_valid && self.neighbor_counts.len() == self.cells.len() {
+            for &idx in &self.changed {
+                candidates.push(idx as usize);
+                self.for_each_neighbor(idx as usize, |n| candidates.push(n));
+            }
+            candidates.sort_unstable();
+            candidates.dedup();
+        } else {
+            self.rebuild_neighbor_counts();
+            candidates.extend(0..self.cells.len());
+        }
+
+        self.changed.clear();
+        // Newborns' species are picked before any cell is updated, so they
+        // see the previous generation like the dense tick does.
+        let mut updates = Vec::new();
+        for idx in candidates {
+            if self.frozen_at(idx) {
+                continue;
+            }
+            let count = self.neighbor_counts[idx] as usize;
+            let cell = self.cells[idx];
+            let alive = if cell.is_alive() {
+                self.rule.survive[count]
+            } else {
+                self.rule.birth[count]
+            };
+            if alive != cell.is_alive() {
+                self.changed.push(idx as u32);
+                updates.push(if alive {
+                    let (row, col) = self.coords(idx);
+                    self.birth_species(row, col)
+                } else {
+                    Cell::Dead
+                });
+            }
+        }
+
+        for (i, &cell) in updates.iter().enumerate() {
+            let idx = self.changed[i] as usize;
+            self.cells[idx] = cell;
+            let born = cell.is_alive();
+            let mut counts = std::mem::take(&mut self.neighbor_counts);
+            self.for_each_neighbor(idx, |n| {
+                if born {
+                    counts[n] += 1;
+                } else {
+                    counts[n] -= 1;
+                }
+            });
+            self.neighbor_counts = counts;
+        }
+
+        self.ages.resize(self.cells.len(), 0);
+        for (age, &cell) in self.ages.iter_mut().zip(self.cells.iter()) {
+            *age = if cell.is_alive() {
+                age.saturating_add(1)
+            } else {
+                0
+            };
+        }
+        for &idx in &self.changed {
+            self.ages[idx as usize] = 0;
+        }
+
+        self.tiles_valid = false;
+        self.generation += 1;
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Same as `tick`, but keeps a running live-neighbor count per cell and
+    /// only re-evaluates cells next to the previous generation's changes.
+    /// Much faster on large, mostly still boards; the first call after an
+    /// edit or a dense `tick` pays for a full recount.
+    pub fn tick_sparse(&mut self) {
+        self.while_ticking(|universe| {
+            let timer = universe.tick_timer("universe_tick_sparse");
+            universe.push_history();
+            universe.advance_sparse();
+            universe.end_generation();
+            universe.finish_tick(timer);
+        });
+    }
+}
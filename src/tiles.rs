@@ -0,0 +1,118 @@
+//! Skipping quiescent parts of the board during a dense tick. The board is
+//! split into square tiles, and only tiles in which a cell changed last
+//! generation, plus their neighbors, are recomputed.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Universe};
+
+/// Side length of a tile, in cells.
+const TILE: u32 = 32;
+
+impl Universe {
+    fn tile_grid(&self) -> (u32, u32) {
+        (self.width.div_ceil(TILE), self.height.div_ceil(TILE))
+    }
+
+    /// Whether a cell can only change when one of its eight neighbors changed
+    /// the generation before, which is what makes skipping tiles exact.
+    /// Larger neighborhoods and Generations rules always take the full path,
+    /// and so do boards big enough for the parallel tick, which beats a
+    /// serial pass over the active tiles.
+    fn tiles_supported(&self) -> bool {
+        // A twisted wrap shifts columns by an amount that needn't line up
+        // with tile boundaries.
+        self.radius == 1
+            && self.num_states == 2
+            && (self.twist == 0 || !self.boundary.wraps().0)
+            && !self.steps_in_parallel()
+    }
+
+    /// Whether `active_tiles` can be trusted to pick what to recompute.
+    pub(crate) fn can_skip_tiles(&self) -> bool {
+        let (tiles_wide, tiles_high) = self.tile_grid();
+        self.tiles_valid
+            && self.tiles_supported()
+            && self.active_tiles.len() == tiles_wide as usize * tiles_high as usize
+    }
+
+    /// Fills `next_gen` by copying the current cells and recomputing only the
+    /// tiles that are active or next to an active tile.
+    pub(crate) fn fill_next_tiles(&self, next_gen: &mut [Cell]) {
+        next_gen.copy_from_slice(&self.cells);
+        let (tiles_wide, tiles_high) = self.tile_grid();
+        for tile_row in 0..tiles_high {
+            for tile_col in 0..tiles_wide {
+                if !self.near_active_tile(tile_row, tile_col) {
+                    continue;
+                }
+                let top = tile_row * TILE;
+                let left = tile_col * TILE;
+                for row in top..(top + TILE).min(self.height) {
+                    for col in left..(left + TILE).min(self.width) {
+                        next_gen[self.get_index(row, col)] = self.next_state(row, col);
+                    }
+                }
+            }
+        }
+    }
+
+    fn near_active_tile(&self, tile_row: u32, tile_col: u32) -> bool {
+        let (tiles_wide, tiles_high) = self.tile_grid();
+        for d_r in -1..=1i64 {
+            for d_c in -1..=1i64 {
+                // Edge cells wrap to the last row or column of cells, which
+                // is always in the last tile even when it is partial, so
+                // wrapping over tiles matches wrapping over cells.
+                let (row, col) = match self.boundary.resolve(
+                    tile_row as i64 + d_r,
+                    tile_col as i64 + d_c,
+                    tiles_high as i64,
+                    tiles_wide as i64,
+                    0,
+                ) {
+                    Some(tile) => tile,
+                    None => continue,
+                };
+                if self.active_tiles[row as usize * tiles_wide as usize + col as usize] {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Marks the tiles containing the cells that just changed as active.
+    pub(crate) fn update_active_tiles(&mut self) {
+        if !self.tiles_supported() {
+            self.tiles_valid = false;
+            return;
+        }
+        let (tiles_wide, tiles_high) = self.tile_grid();
+        self.active_tiles.clear();
+        self.active_tiles
+            .resize(tiles_wide as usize * tiles_high as usize, false);
+        for i in 0..self.changed.len() {
+            let (row, col) = self.coords(self.changed[i] as usize);
+            let tile = (row / TILE) as usize * tiles_wide as usize + (col / TILE) as usize;
+            self.active_tiles[tile] = true;
+        }
+        self.tiles_valid = true;
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// How many of the board's 32×32 tiles had a cell change in the most
+    /// recent `tick`. Only these and the tiles around them are recomputed
+    /// next time, so a low count means most of the board is being skipped.
+    /// After an edit every tile is recomputed once, and with the `rayon`
+    /// feature boards of 64K cells or more always recompute every tile.
+    pub fn active_tile_count(&self) -> u32 {
+        if !self.can_skip_tiles() {
+            let (tiles_wide, tiles_high) = self.tile_grid();
+            return tiles_wide * tiles_high;
+        }
+        self.active_tiles.iter().filter(|&&active| active).count() as u32
+    }
+}
@@ -0,0 +1,119 @@
+//! Rolling statistics over recent `tick` durations, for spotting jank, and
+//! optional JS and Rust callbacks that receive each one.
+
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Timer, Universe};
+
+/// How many recent ticks the statistics cover.
+pub const WINDOW: usize = 60;
+
+/// A Rust tick callback; see `Universe::set_tick_hook`.
+#[cfg(not(feature = "rayon"))]
+pub type TickHook = Box<dyn FnMut(&mut Universe, f64)>;
+
+#[derive(Debug, Default)]
+pub struct TickTimings {
+    durations: VecDeque<f64>,
+}
+
+impl TickTimings {
+    pub fn push(&mut self, ms: f64) {
+        if self.durations.len() == WINDOW {
+            self.durations.pop_front();
+        }
+        self.durations.push_back(ms);
+    }
+
+    pub fn reset(&mut self) {
+        self.durations.clear();
+    }
+}
+
+impl Universe {
+    /// Starts timing a tick, logged to the console as `name` unless a tick
+    /// callback will report the duration instead.
+    pub(crate) fn tick_timer(&self, name: &'static str) -> Timer<'static> {
+        #[cfg(not(feature = "rayon"))]
+        {
+            if self.tick_callback.is_some() {
+                return Timer::silent();
+            }
+        }
+        Timer::new(name)
+    }
+
+    /// Records the duration of a tick started with `tick_timer` and passes it
+    /// to the tick callbacks, if any.
+    pub(crate) fn finish_tick(&mut self, timer: Timer) {
+        let ms = timer.elapsed_ms();
+        self.tick_timings.push(ms);
+        #[cfg(not(feature = "rayon"))]
+        {
+            if let Some(callback) = &self.tick_callback {
+                // A throwing callback shouldn't undo a tick that already
+                // happened, so its exception is dropped.
+                let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(ms));
+            }
+            if let Some(mut hook) = self.tick_hook.take() {
+                hook(self, ms);
+                if self.tick_hook.is_none() {
+                    self.tick_hook = Some(hook);
+                }
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// `[last_ms, avg_ms, max_ms]` over the most recent ticks (up to 60),
+    /// all zero before the first tick. The window restarts when the board
+    /// is cleared or resized.
+    pub fn timing_stats(&self) -> Box<[f64]> {
+        let durations = &self.tick_timings.durations;
+        let last = durations.back().copied().unwrap_or(0.0);
+        let max = durations.iter().copied().fold(0.0, f64::max);
+        let avg = if durations.is_empty() {
+            0.0
+        } else {
+            durations.iter().sum::<f64>() / durations.len() as f64
+        };
+        Box::new([last, avg, max])
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+#[wasm_bindgen]
+impl Universe {
+    /// Calls `callback` with the duration in milliseconds after every `tick`
+    /// and `tick_sparse`, for feeding your own telemetry. `tick_many`,
+    /// `tick_until_stable` and `tick_region` report once per call. While one is set
+    /// ticks are no longer logged with `console.time`. Not available with
+    /// the `rayon` feature.
+    pub fn set_tick_callback(&mut self, callback: js_sys::Function) {
+        self.tick_callback = Some(callback);
+    }
+
+    /// Removes the tick callback and goes back to console timing.
+    pub fn clear_tick_callback(&mut self) {
+        self.tick_callback = None;
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+impl Universe {
+    /// The Rust counterpart of `set_tick_callback`: calls `hook` with the
+    /// universe and the duration in milliseconds after every tick, like the
+    /// JS callback. The tick is still in progress while it runs, so methods
+    /// that replace the board, such as `resize`, fail.
+    pub fn set_tick_hook(&mut self, hook: impl FnMut(&mut Universe, f64) + 'static) {
+        self.tick_hook = Some(Box::new(hook));
+    }
+
+    pub fn clear_tick_hook(&mut self) {
+        self.tick_hook = None;
+    }
+}
@@ -0,0 +1,113 @@
+//! Whole-board geometric transforms for editor tools.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Cell, Universe};
+
+impl Universe {
+    /// Moves every cell to `map(row, col)` on a `width`×`height` board,
+    /// carrying its age, Generations state and frozen flag along with it. Cells mapped to
+    /// `None` are dropped, and any position nothing maps to is left dead.
+    fn remap<F>(&mut self, width: u32, height: u32, map: F)
+    where
+        F: Fn(u32, u32) -> Option<(u32, u32)>,
+    {
+        let len = width as usize * height as usize;
+        let mut cells = vec![Cell::Dead; len];
+        let mut ages = vec![0; len];
+        let mut states = vec![0; len];
+        let mut frozen = vec![false; if self.frozen.is_empty() { 0 } else { len }];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let old = self.get_index(row, col);
+                let (new_row, new_col) = match map(row, col) {
+                    Some(target) => target,
+                    None => continue,
+                };
+                let new = self.layout.index(width, height, new_row, new_col);
+                cells[new] = self.cells[old];
+                ages[new] = self.ages[old];
+                states[new] = self.states[old];
+                if !frozen.is_empty() {
+                    frozen[new] = self.frozen[old];
+                }
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.ages = ages;
+        self.states = states;
+        self.frozen = frozen;
+        self.changed.clear();
+        self.rebuild_neighbor_tables();
+        self.track_buffer();
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    /// Rotates the board 90° clockwise, swapping its width and height. Fails
+    /// during a tick.
+    pub fn rotate_cw(&mut self) -> Result<(), JsValue> {
+        self.check_not_ticking()
+            .map_err(|e| JsValue::from_str(&e))?;
+        self.record_history();
+        let height = self.height;
+        self.remap(height, self.width, |row, col| Some((col, height - 1 - row)));
+        Ok(())
+    }
+
+    /// Mirrors the board left to right.
+    pub fn flip_horizontal(&mut self) {
+        self.record_history();
+        let width = self.width;
+        self.remap(width, self.height, |row, col| Some((row, width - 1 - col)));
+    }
+
+    /// Mirrors the board top to bottom.
+    pub fn flip_vertical(&mut self) {
+        self.record_history();
+        let height = self.height;
+        self.remap(self.width, height, |row, col| Some((height - 1 - row, col)));
+    }
+
+    /// Scrolls the whole board by `d_row` rows and `d_col` columns, e.g. to
+    /// follow a glider. Along a wrapping edge cells wrap around; past a dead
+    /// one they fall off the far edge and dead cells shift in.
+    pub fn shift(&mut self, d_row: i32, d_col: i32) {
+        self.record_history();
+        let (width, height) = (self.width as i64, self.height as i64);
+        let (boundary, twist) = (self.boundary, self.twist);
+        self.remap(self.width, self.height, |row, col| {
+            boundary
+                .resolve(
+                    row as i64 + d_row as i64,
+                    col as i64 + d_col as i64,
+                    height,
+                    width,
+                    twist,
+                )
+                .map(|(row, col)| (row as u32, col as u32))
+        });
+    }
+
+    /// Reflects the top-left quadrant into the other three: mirrored left to
+    /// right into the top-right, top to bottom into the bottom-left, and both
+    /// ways into the bottom-right. With an odd dimension the middle row or
+    /// column belongs to the top-left quadrant and stays as it is.
+    pub fn mirror_quadrant(&mut self) {
+        self.record_history();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let src_row = row.min(self.height - 1 - row);
+                let src_col = col.min(self.width - 1 - col);
+                let cell = self.cells[self.get_index(src_row, src_col)];
+                let idx = self.get_index(row, col);
+                if self.cells[idx] != cell {
+                    self.write_cell(idx, cell);
+                }
+            }
+        }
+    }
+}
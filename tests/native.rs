@@ -0,0 +1,1583 @@
+//! Test suite run natively with `cargo test`, no browser required.
+
+extern crate wasm_game_of_life;
+
+use wasm_game_of_life::{
+    BoundaryMode, Cell, Layout, Neighborhood, PackedUniverse, Universe, UniverseBuilder,
+};
+
+pub fn input_spaceship() -> Universe {
+    let mut universe = Universe::new();
+    universe.set_width(6).unwrap();
+    universe.set_height(6).unwrap();
+    universe.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+    universe
+}
+
+pub fn expected_spaceship() -> Universe {
+    let mut universe = Universe::new();
+    universe.set_width(6).unwrap();
+    universe.set_height(6).unwrap();
+    universe.set_cells(&[(2, 1), (2, 3), (3, 2), (3, 3), (4, 2)]);
+    universe
+}
+
+#[test]
+pub fn test_tick() {
+    let mut input_universe = input_spaceship();
+    let expected_universe = expected_spaceship();
+
+    input_universe.tick();
+    assert_eq!(&input_universe.get_cells(), &expected_universe.get_cells());
+    assert_eq!(input_universe.generation(), 1);
+}
+
+#[test]
+pub fn test_render_with() {
+    let universe = Universe::from_matrix("010\n001").unwrap();
+    assert_eq!(universe.render_with('#', '.'), ".#.\n..#\n");
+    assert_eq!(universe.render_with('◼', '◻'), universe.render());
+}
+
+#[test]
+pub fn test_tick_matches_packed() {
+    let mut universe = Universe::new();
+    universe.resize(37, 23).unwrap();
+    universe.randomize(42, 0.35);
+    let mut packed = PackedUniverse::from_universe(&universe).unwrap();
+
+    for _ in 0..20 {
+        universe.tick();
+        packed.tick();
+    }
+    for row in 0..23 {
+        for col in 0..37 {
+            assert_eq!(universe.get_cell(row, col), packed.get_cell(row, col));
+        }
+    }
+}
+
+#[test]
+pub fn test_undo_redo() {
+    let mut universe = input_spaceship();
+    universe.tick();
+    universe.toggle_cell(0, 0);
+
+    assert!(universe.undo().unwrap());
+    assert_eq!(&universe.get_cells(), &expected_spaceship().get_cells());
+    assert!(universe.undo().unwrap());
+    assert_eq!(&universe.get_cells(), &input_spaceship().get_cells());
+    assert_eq!(universe.generation(), 0);
+
+    assert!(universe.redo().unwrap());
+    assert_eq!(&universe.get_cells(), &expected_spaceship().get_cells());
+    assert_eq!(universe.generation(), 1);
+}
+
+#[test]
+pub fn test_from_plaintext() {
+    let universe = Universe::from_plaintext("!Name: Glider\n\n..O\n...O\n.OOO\n").unwrap();
+    assert_eq!(universe.width(), 4);
+    assert_eq!(universe.height(), 4);
+    assert!(universe.get_cell(1, 2));
+    assert!(universe.get_cell(3, 3));
+    assert_eq!(universe.population(), 5);
+}
+
+#[test]
+pub fn test_generations_decay() {
+    // Brian's Brain: B2/S/C3.
+    let mut universe = Universe::from_plaintext(".....\n.O.O.\n.....").unwrap();
+    universe.set_rule("B2/S").unwrap();
+    universe.set_num_states(3);
+
+    universe.tick();
+    assert_eq!(universe.get_state(1, 1), 2);
+    assert_eq!(universe.get_state(0, 2), 1);
+    assert_eq!(universe.get_state(1, 2), 1);
+
+    universe.tick();
+    assert_eq!(universe.get_state(1, 1), 0);
+    assert_eq!(universe.get_state(0, 2), 2);
+}
+
+#[test]
+pub fn test_builder() {
+    let universe = UniverseBuilder::new()
+        .width(20)
+        .height(10)
+        .rule("B36/S23")
+        .boundary(BoundaryMode::Fixed)
+        .seed(3)
+        .density(0.25)
+        .try_build()
+        .unwrap();
+    assert_eq!(universe.width(), 20);
+    assert_eq!(universe.height(), 10);
+    assert_eq!(universe.boundary_mode(), BoundaryMode::Fixed);
+
+    let mut expected = Universe::new();
+    expected.resize(20, 10).unwrap();
+    expected.randomize(3, 0.25);
+    assert_eq!(&universe.get_cells(), &expected.get_cells());
+}
+
+#[test]
+pub fn test_rotate_cw() {
+    let mut universe = Universe::from_plaintext("OO.\n...").unwrap();
+    universe.rotate_cw().unwrap();
+    assert_eq!(universe.width(), 2);
+    assert_eq!(universe.height(), 3);
+    assert!(universe.get_cell(0, 1));
+    assert!(universe.get_cell(1, 1));
+    assert_eq!(universe.population(), 2);
+}
+
+#[test]
+pub fn test_radius() {
+    let mut universe = Universe::with_dimensions(7, 7).unwrap();
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    universe.set_cells(&[(0, 0), (1, 1), (3, 3), (5, 5), (6, 6)]);
+    assert_eq!(universe.neighbors_alive(3, 3), 0);
+    universe.set_radius(2);
+    assert_eq!(universe.neighbors_alive(3, 3), 2);
+    universe.set_radius(3);
+    assert_eq!(universe.neighbors_alive(3, 3), 4);
+}
+
+#[test]
+pub fn test_snapshot_restore() {
+    let mut universe = input_spaceship();
+    let snapshot = universe.snapshot();
+
+    universe.tick_many(4);
+    universe.resize(10, 10).unwrap();
+    universe.restore(&snapshot).unwrap();
+
+    assert_eq!(universe.width(), 6);
+    assert_eq!(universe.generation(), 0);
+    assert_eq!(&universe.get_cells(), &input_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_tick_sparse_matches_dense() {
+    for &mode in &[
+        BoundaryMode::Toroidal,
+        BoundaryMode::Fixed,
+        BoundaryMode::CylinderHorizontal,
+        BoundaryMode::CylinderVertical,
+    ] {
+        let mut dense = Universe::new();
+        dense.resize(41, 29).unwrap();
+        dense.set_boundary_mode(mode);
+        dense.randomize(7, 0.3);
+        let mut sparse = Universe::new();
+        sparse.resize(41, 29).unwrap();
+        sparse.set_boundary_mode(mode);
+        sparse.randomize(7, 0.3);
+
+        for step in 0..30 {
+            if step == 15 {
+                dense.toggle_cell(3, 4);
+                sparse.toggle_cell(3, 4);
+            }
+            dense.tick();
+            sparse.tick_sparse();
+            assert_eq!(sparse.get_cells(), dense.get_cells());
+            assert_eq!(sparse.changed_cells(), dense.changed_cells());
+            assert_eq!(sparse.get_age(10, 10), dense.get_age(10, 10));
+        }
+        assert_eq!(sparse.generation(), dense.generation());
+    }
+}
+
+#[test]
+pub fn test_live_cells() {
+    let universe = input_spaceship();
+    let live: Vec<(u32, u32)> = universe.live_cells().collect();
+    assert_eq!(live, vec![(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+    assert_eq!(universe.live_coords(), vec![1, 2, 2, 3, 3, 1, 3, 2, 3, 3]);
+}
+
+#[test]
+pub fn test_cell_bool_conversions() {
+    assert_eq!(Cell::from(true), Cell::Alive);
+    assert_eq!(Cell::from(false), Cell::Dead);
+    assert!(bool::from(Cell::Alive));
+    assert!(!Cell::Dead.is_alive());
+}
+
+#[test]
+pub fn test_population_history() {
+    let mut universe = input_spaceship();
+    universe.set_history_capacity(3);
+    universe.tick_many(5);
+    assert_eq!(universe.population_history(), vec![5, 5, 5]);
+
+    universe.set_history_capacity(0);
+    universe.tick();
+    assert!(universe.population_history().is_empty());
+}
+
+#[test]
+pub fn test_matrix_round_trip() {
+    let universe = Universe::from_matrix("010\n111\n000").unwrap();
+    assert_eq!((universe.width(), universe.height()), (3, 3));
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.to_matrix(), "010\n111\n000\n");
+    assert!(Universe::from_matrix(&universe.to_matrix()).unwrap() == universe);
+}
+
+#[test]
+pub fn test_noise_is_deterministic() {
+    let run = |seed| {
+        let mut universe = Universe::new();
+        universe.resize(16, 16).unwrap();
+        universe.set_noise(seed, 0.05);
+        universe.tick_many(10);
+        universe.get_cells().to_vec()
+    };
+    assert_eq!(run(3), run(3));
+    assert_ne!(run(3), run(4));
+
+    let mut quiet = input_spaceship();
+    quiet.set_noise(3, 0.0);
+    quiet.tick();
+    assert_eq!(quiet.get_cells(), expected_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_noise_keeps_changed_cells_accurate() {
+    let mut universe = Universe::new();
+    universe.resize(16, 16).unwrap();
+    universe.set_noise(9, 0.1);
+    for _ in 0..5 {
+        let before = universe.get_cells().to_vec();
+        universe.tick();
+        let expected: Vec<u32> = (0..before.len())
+            .filter(|&idx| before[idx] != universe.get_cells()[idx])
+            .map(|idx| idx as u32)
+            .collect();
+        assert_eq!(universe.changed_cells(), expected);
+    }
+}
+
+#[test]
+pub fn test_diff_packed_decodes_to_changed_cells() {
+    let mut universe = Universe::new_demo();
+    for _ in 0..3 {
+        universe.tick();
+        let packed = universe.diff_packed();
+        let indices: Vec<u32> = packed.iter().map(|&entry| entry >> 1).collect();
+        assert_eq!(indices, universe.changed_cells());
+        for (&entry, &idx) in packed.iter().zip(&indices) {
+            assert_eq!(
+                entry & 1 == 1,
+                universe.get_cells()[idx as usize].is_alive()
+            );
+        }
+    }
+}
+
+#[test]
+pub fn test_rule_string() {
+    let mut universe = Universe::new();
+    assert_eq!(universe.rule_string(), "B3/S23");
+    universe.set_rule("B36/S23").unwrap();
+    assert_eq!(universe.rule_string(), "B36/S23");
+    universe.set_rule("s32/b2").unwrap();
+    assert_eq!(universe.rule_string(), "B2/S23");
+    universe.set_rule("B2/S").unwrap();
+    assert_eq!(universe.rule_string(), "B2/S");
+}
+
+#[test]
+pub fn test_neighbor_histogram() {
+    let mut universe = Universe::from_matrix("000\n010\n000").unwrap();
+    assert_eq!(
+        universe.neighbor_histogram(),
+        vec![1, 8, 0, 0, 0, 0, 0, 0, 0]
+    );
+
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    universe.resize(4, 4).unwrap();
+    assert_eq!(
+        universe.neighbor_histogram(),
+        vec![8, 8, 0, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+pub fn test_builder_rejects_oversized_boards() {
+    let build = |width, height| {
+        UniverseBuilder::new()
+            .width(width)
+            .height(height)
+            .try_build()
+    };
+    assert!(build(0, 64).is_err());
+    assert!(build(65536, 65536).is_err());
+    assert!(build(u32::MAX, 2).is_err());
+}
+
+#[test]
+pub fn test_column_major_layout() {
+    let build = |layout| {
+        UniverseBuilder::new()
+            .width(23)
+            .height(17)
+            .layout(layout)
+            .seed(5)
+            .density(0.4)
+            .try_build()
+            .unwrap()
+    };
+    let mut rows = build(Layout::RowMajor);
+    let mut cols = build(Layout::ColumnMajor);
+    assert_eq!(cols.layout(), Layout::ColumnMajor);
+    assert!(rows == cols);
+
+    for _ in 0..10 {
+        rows.tick();
+        cols.tick_sparse();
+    }
+    cols.rotate_cw().unwrap();
+    rows.rotate_cw().unwrap();
+    assert!(rows == cols);
+    assert_eq!(rows.to_rle(), cols.to_rle());
+    assert_eq!(rows.state_hash(), cols.state_hash());
+    assert_eq!(rows.live_coords(), cols.live_coords());
+}
+
+#[test]
+pub fn test_tick_region() {
+    // A blinker inside the region and a lone cell outside it that a full
+    // tick would kill.
+    let mut universe = Universe::from_matrix("00000000\n01110000\n00000000\n00000001").unwrap();
+    universe.tick_region(0, 0, 3, 4);
+    assert_eq!(
+        universe.to_matrix(),
+        "00100000\n00100000\n00100000\n00000001\n"
+    );
+    assert_eq!(universe.generation(), 1);
+}
+
+#[test]
+pub fn test_from_coords() {
+    let universe = Universe::from_coords(6, 6, &[1, 2, 2, 3, 3, 1, 3, 2, 3, 3]).unwrap();
+    assert_eq!(universe.get_cells(), input_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_step_reports_changes() {
+    let mut universe = Universe::from_matrix("0000\n0110\n0110\n0000").unwrap();
+    assert!(!universe.step());
+
+    let mut universe = input_spaceship();
+    assert!(universe.step());
+    assert_eq!(universe.get_cells(), expected_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_edits_end_stability() {
+    let mut universe =
+        Universe::from_matrix("000000\n000000\n001100\n001100\n000000\n000000").unwrap();
+    assert!(!universe.is_stable());
+    universe.tick();
+    assert!(universe.is_stable());
+
+    universe.toggle_cell(0, 0);
+    assert!(!universe.is_stable());
+    assert!(universe.changed_cells().is_empty());
+    universe.tick();
+    assert!(!universe.is_stable());
+    assert_eq!(universe.changed_cells(), vec![0]);
+
+    assert!(universe.undo().unwrap());
+    assert!(!universe.is_stable());
+}
+
+#[test]
+pub fn test_bytes_round_trip() {
+    let universe = input_spaceship();
+    let bytes = universe.to_bytes();
+    assert_eq!(bytes.len(), 36);
+    assert_eq!(&bytes[6..12], &[0, 0, 1, 0, 0, 0]);
+    assert!(Universe::from_bytes(6, 6, &bytes).unwrap() == universe);
+}
+
+#[test]
+pub fn test_timing_stats() {
+    let mut universe = input_spaceship();
+    assert_eq!(&*universe.timing_stats(), &[0.0, 0.0, 0.0]);
+
+    universe.tick();
+    universe.tick();
+    let stats = universe.timing_stats();
+    assert!(stats[0] >= 0.0 && stats[1] <= stats[2]);
+
+    universe.clear();
+    assert_eq!(&*universe.timing_stats(), &[0.0, 0.0, 0.0]);
+}
+
+#[test]
+pub fn test_shift() {
+    let mut universe = Universe::from_matrix("100\n000\n001").unwrap();
+    universe.shift(1, -1);
+    assert_eq!(universe.to_matrix(), "010\n001\n000\n");
+
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    universe.shift(0, 1);
+    assert_eq!(universe.to_matrix(), "001\n000\n000\n");
+}
+
+#[test]
+pub fn test_out_of_range_coordinates() {
+    let mut universe = input_spaceship();
+    assert_eq!(universe.try_index(1, 2), Some(8));
+    assert_eq!(universe.try_index(6, 0), None);
+    assert_eq!(universe.try_index(0, 6), None);
+
+    universe.toggle_cell(6, 6);
+    universe.set_cells(&[(0, 9)]);
+    assert!(!universe.get_cell(9, 9));
+    assert_eq!(universe.get_cells(), input_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_invert() {
+    let mut universe = input_spaceship();
+    universe.tick();
+    universe.invert();
+    assert_eq!(universe.population(), 36 - 5);
+    assert_eq!(universe.generation(), 1);
+    universe.invert();
+    assert_eq!(universe.get_cells(), expected_spaceship().get_cells());
+}
+
+#[test]
+pub fn test_alive_row_spans() {
+    let universe = Universe::from_matrix("0110\n0000\n1011\n1111").unwrap();
+    assert_eq!(
+        universe.alive_row_spans(),
+        vec![0, 1, 2, 2, 0, 1, 2, 2, 2, 3, 0, 4]
+    );
+}
+
+#[test]
+pub fn test_new_is_empty() {
+    assert_eq!(Universe::new().population(), 0);
+
+    let demo = Universe::new_demo();
+    assert_eq!((demo.width(), demo.height()), (128, 128));
+    assert!(demo.get_cell(0, 0) && !demo.get_cell(0, 1) && demo.get_cell(0, 7));
+}
+
+#[test]
+pub fn test_density_and_block_entropy() {
+    let universe = Universe::from_matrix("1100\n1100\n0000\n0000").unwrap();
+    assert_eq!(universe.density(), 0.25);
+    // One all-alive tile and three all-dead ones.
+    let expected = -(0.25f64 * 0.25f64.log2() + 0.75 * 0.75f64.log2());
+    assert!((universe.block_entropy(2).unwrap() - expected).abs() < 1e-12);
+    assert_eq!(universe.block_entropy(1).unwrap(), expected);
+    assert_eq!(universe.block_entropy(4).unwrap(), 0.0);
+}
+
+#[test]
+pub fn test_from_life106() {
+    let text = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+    let universe = Universe::from_life106(text, 5, 5, false).unwrap();
+    assert_eq!(universe.to_matrix(), "01000\n00100\n11100\n00000\n00000\n");
+
+    let clipped = Universe::from_life106(text, 2, 2, true).unwrap();
+    assert_eq!(clipped.to_matrix(), "01\n00\n");
+}
+
+#[test]
+pub fn test_set_cell() {
+    let mut universe = Universe::from_matrix("00\n00").unwrap();
+    universe.set_cell(0, 1, true);
+    universe.set_cell(0, 1, true);
+    universe.set_cell(1, 0, false);
+    universe.set_cell(2, 2, true);
+    assert_eq!(universe.to_matrix(), "01\n00\n");
+    universe.set_cell(0, 1, false);
+    assert_eq!(universe.population(), 0);
+}
+
+#[test]
+pub fn test_frame_checksum() {
+    let mut a = UniverseBuilder::new().seed(11).try_build().unwrap();
+    let mut b = UniverseBuilder::new().seed(11).try_build().unwrap();
+    for _ in 0..5 {
+        assert_eq!(a.frame_checksum(), b.frame_checksum());
+        a.tick();
+        b.tick();
+    }
+
+    let before = a.frame_checksum();
+    a.toggle_cell(0, 0);
+    assert_ne!(a.frame_checksum(), before);
+}
+
+#[test]
+pub fn test_prepare_is_transparent() {
+    let mut prepared = input_spaceship();
+    prepared.resize(8, 8).unwrap();
+    prepared.prepare();
+    prepared.prepare();
+    let mut plain = input_spaceship();
+    plain.resize(8, 8).unwrap();
+
+    prepared.tick();
+    plain.tick();
+    assert_eq!(prepared.get_cells(), plain.get_cells());
+}
+
+#[test]
+pub fn test_tile_skipping_matches_full_tick() {
+    for &mode in &[
+        BoundaryMode::Toroidal,
+        BoundaryMode::Fixed,
+        BoundaryMode::CylinderHorizontal,
+        BoundaryMode::CylinderVertical,
+    ] {
+        let build = || {
+            let mut universe = UniverseBuilder::new()
+                .width(100)
+                .height(70)
+                .boundary(mode)
+                .try_build()
+                .unwrap();
+            // A few gliders and a random patch, so most tiles stay quiet.
+            universe.insert_glider(1, 1);
+            universe.insert_glider(40, 95);
+            universe.insert_glider(68, 60);
+            let patch: Vec<u8> = (0..100).map(|i| (i * 7 % 3 == 0) as u8).collect();
+            universe.insert_pattern(30, 30, 10, 10, &patch);
+            universe
+        };
+        let mut tiled = build();
+        let mut full = build();
+
+        for _ in 0..60 {
+            tiled.tick();
+            // Changing the rule, even to the same one, forces a full recompute.
+            full.set_rule("B3/S23").unwrap();
+            full.tick();
+            assert_eq!(tiled.get_cells(), full.get_cells());
+            assert_eq!(tiled.changed_cells(), full.changed_cells());
+        }
+        assert!(tiled.active_tile_count() < 12);
+    }
+}
+
+#[test]
+pub fn test_mirror_quadrant() {
+    let mut universe = Universe::from_matrix("10000\n01000\n00000\n00000").unwrap();
+    universe.mirror_quadrant();
+    assert_eq!(universe.to_matrix(), "10001\n01010\n01010\n10001\n");
+
+    let mut odd = Universe::from_matrix("011\n100\n111").unwrap();
+    odd.mirror_quadrant();
+    assert_eq!(odd.to_matrix(), "010\n101\n010\n");
+}
+
+#[test]
+pub fn test_base64_round_trip() {
+    for &(width, height) in &[(6, 6), (7, 3), (1, 1), (64, 33)] {
+        let universe = UniverseBuilder::new()
+            .width(width)
+            .height(height)
+            .seed(width as u64)
+            .try_build()
+            .unwrap();
+        let saved = universe.to_base64();
+        assert!(saved
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert!(Universe::from_base64(&saved).unwrap() == universe);
+    }
+    assert_eq!(
+        Universe::from_matrix("1").unwrap().to_base64(),
+        "AQAAAAEAAAAB"
+    );
+}
+
+#[test]
+pub fn test_find_gliders() {
+    let mut universe = Universe::with_dimensions(20, 20).unwrap();
+    universe.insert_glider(2, 3);
+    assert_eq!(universe.find_gliders(), vec![2, 3]);
+    for _ in 0..4 {
+        universe.tick();
+        assert_eq!(universe.find_gliders().len(), 2);
+    }
+    assert_eq!(universe.find_gliders(), vec![3, 4]);
+
+    universe.flip_horizontal();
+    universe.insert_blinker(15, 15);
+    assert_eq!(universe.find_gliders(), vec![3, 13]);
+
+    // A neighbor touching the window means it is no longer a lone glider.
+    universe.set_cell(2, 14, true);
+    assert!(universe.find_gliders().is_empty());
+}
+
+#[test]
+pub fn test_steps_matches_tick() {
+    let mut stepped = Universe::new_demo();
+    let mut ticked = Universe::new_demo();
+    let populations: Vec<u32> = stepped.steps().take(5).collect();
+    assert_eq!(populations.len(), 5);
+    for &population in &populations {
+        ticked.tick();
+        assert_eq!(ticked.population(), population);
+    }
+    assert_eq!(stepped.generation(), 5);
+    assert!(stepped == ticked);
+
+    let mut blinker = Universe::with_dimensions(5, 5).unwrap();
+    blinker.insert_blinker(2, 1);
+    assert!(blinker.steps().take(10).all(|population| population == 3));
+}
+
+#[test]
+pub fn test_cylinder_boundaries() {
+    // A south-east glider on a cylinder that wraps left to right slides
+    // through the right edge intact, then settles into a block against the
+    // dead bottom.
+    let mut universe = UniverseBuilder::new()
+        .width(10)
+        .height(12)
+        .boundary(BoundaryMode::CylinderHorizontal)
+        .try_build()
+        .unwrap();
+    universe.insert_glider(0, 6);
+    for _ in 0..24 {
+        universe.tick();
+        assert_eq!(universe.population(), 5);
+    }
+    assert_eq!(universe.find_gliders(), vec![6, 2]);
+    for _ in 0..40 {
+        universe.tick();
+    }
+    assert!(universe.find_gliders().is_empty());
+    assert_eq!(universe.live_coords(), vec![10, 6, 10, 7, 11, 6, 11, 7]);
+
+    // The same run transposed: wrapping top to bottom, dead on the right.
+    let mut universe = UniverseBuilder::new()
+        .width(12)
+        .height(10)
+        .boundary(BoundaryMode::CylinderVertical)
+        .try_build()
+        .unwrap();
+    universe.insert_glider(6, 0);
+    for _ in 0..24 {
+        universe.tick();
+        assert_eq!(universe.population(), 5);
+    }
+    assert_eq!(universe.find_gliders(), vec![2, 6]);
+    for _ in 0..40 {
+        universe.tick();
+    }
+    assert!(universe.find_gliders().is_empty());
+    assert_eq!(universe.live_coords(), vec![7, 10, 7, 11, 8, 10, 8, 11]);
+}
+
+#[test]
+pub fn test_resize_centered() {
+    let mut universe = Universe::with_dimensions(6, 6).unwrap();
+    universe.insert_glider(1, 1);
+    let before = universe.live_bounds().unwrap();
+    assert_eq!(&before[..], &[1, 1, 3, 3]);
+
+    // Growing by 10 each way adds 5 on every side, so the glider stays in
+    // the middle of the board.
+    universe.resize_centered(16, 16).unwrap();
+    assert_eq!((universe.width(), universe.height()), (16, 16));
+    assert_eq!(&universe.live_bounds().unwrap()[..], &[6, 6, 8, 8]);
+    assert_eq!(universe.population(), 5);
+
+    // Shrinking crops evenly from both sides and round-trips.
+    universe.resize_centered(6, 6).unwrap();
+    assert_eq!(&universe.live_bounds().unwrap()[..], &before[..]);
+
+    universe.resize_centered(3, 3).unwrap();
+    assert_eq!(universe.to_matrix(), "010\n001\n111\n");
+}
+
+#[test]
+pub fn test_tiny_board_wrapping() {
+    let mut single = Universe::from_matrix("1").unwrap();
+    assert_eq!(single.neighbors_alive(0, 0), 8);
+    single.set_boundary_mode(BoundaryMode::Fixed);
+    assert_eq!(single.neighbors_alive(0, 0), 0);
+    single.set_boundary_mode(BoundaryMode::CylinderHorizontal);
+    assert_eq!(single.neighbors_alive(0, 0), 2);
+
+    // On 2×2 the diagonal cell is reached by all four corner offsets and
+    // each orthogonal one twice; the cell itself never is.
+    let square = Universe::from_matrix("10\n01").unwrap();
+    assert_eq!(square.neighbors_alive(0, 0), 4);
+    assert_eq!(square.neighbors_alive(0, 1), 4);
+    let corner = Universe::from_matrix("10\n00").unwrap();
+    assert_eq!(corner.neighbors_alive(0, 0), 0);
+    assert_eq!(corner.neighbors_alive(1, 0), 2);
+
+    // A one-row board sees itself above and below.
+    let row = Universe::from_matrix("01100").unwrap();
+    assert_eq!(row.neighbors_alive(0, 1), 5);
+    assert_eq!(row.neighbors_alive(0, 3), 3);
+
+    // Every tick path applies the same counts.
+    for text in &["1", "10\n01", "11\n10", "01100", "1\n1\n0"] {
+        let mut dense = Universe::from_matrix(text).unwrap();
+        let mut sparse = Universe::from_matrix(text).unwrap();
+        let mut packed = PackedUniverse::from_universe(&dense).unwrap();
+        for _ in 0..4 {
+            dense.tick();
+            sparse.tick_sparse();
+            packed.tick();
+            assert_eq!(sparse.get_cells(), dense.get_cells());
+            for row in 0..dense.height() {
+                for col in 0..dense.width() {
+                    assert_eq!(packed.get_cell(row, col), dense.get_cell(row, col));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_last_births_and_deaths() {
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.insert_blinker(3, 2);
+    assert_eq!((universe.last_births(), universe.last_deaths()), (0, 0));
+    for _ in 0..3 {
+        universe.tick();
+        assert_eq!((universe.last_births(), universe.last_deaths()), (2, 2));
+    }
+
+    // A lone cell dies without any births, and then nothing happens.
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.toggle_cell(4, 4);
+    universe.tick();
+    assert_eq!((universe.last_births(), universe.last_deaths()), (0, 1));
+    universe.tick_sparse();
+    assert_eq!((universe.last_births(), universe.last_deaths()), (0, 0));
+}
+
+#[test]
+pub fn test_display_from_str_round_trip() {
+    let universe = UniverseBuilder::new()
+        .width(9)
+        .height(4)
+        .seed(3)
+        .try_build()
+        .unwrap();
+    let parsed: Universe = universe.to_string().parse().unwrap();
+    assert!(parsed == universe);
+
+    let parsed: Universe = "◻◼\n◼◻".parse().unwrap();
+    assert_eq!(parsed.to_matrix(), "01\n10\n");
+    assert!("◻◼\n◼".parse::<Universe>().is_err());
+    assert!("◻x".parse::<Universe>().is_err());
+    assert!("".parse::<Universe>().is_err());
+}
+
+#[test]
+pub fn test_buffer_generation() {
+    let mut universe = Universe::new_demo();
+    let buffer = (universe.cells(), universe.buffer_generation());
+    universe.tick();
+    universe.tick_sparse();
+    universe.toggle_cell(3, 3);
+    universe.detect_period(4);
+    universe.undo().unwrap();
+    assert_eq!((universe.cells(), universe.buffer_generation()), buffer);
+
+    universe.resize(200, 100).unwrap();
+    assert_ne!(universe.buffer_generation(), buffer.1);
+    let resized = universe.buffer_generation();
+    universe.undo().unwrap();
+    assert_eq!((universe.width(), universe.height()), (128, 128));
+    assert_ne!(universe.buffer_generation(), resized);
+}
+
+#[test]
+pub fn test_render_rgba_downscaled() {
+    let universe = Universe::from_matrix("1100\n1100\n0000\n0001").unwrap();
+    let (alive, dead) = (0xff_ff_ff_ff, 0x00_00_00_ff);
+
+    let pixels = universe.render_rgba_downscaled(2, 2, alive, dead);
+    assert_eq!(pixels.len(), 2 * 2 * 4);
+    assert_eq!(&pixels[0..4], &[255, 255, 255, 255]);
+    assert_eq!(&pixels[4..8], &[0, 0, 0, 255]);
+    assert_eq!(&pixels[12..16], &[64, 64, 64, 255]);
+
+    // Three pixels across four cells: each covers a cell and a third.
+    let pixels = universe.render_rgba_downscaled(3, 1, alive, dead);
+    assert_eq!(pixels.len(), 3 * 4);
+    let shades: Vec<u8> = pixels.chunks(4).map(|p| p[0]).collect();
+    assert_eq!(shades, vec![128, 64, 48]);
+
+    // At full size it matches the plain renderer.
+    assert_eq!(
+        universe.render_rgba_downscaled(4, 4, alive, dead),
+        universe.render_rgba(alive, dead)
+    );
+    assert!(universe
+        .render_rgba_downscaled(0, 3, alive, dead)
+        .is_empty());
+}
+
+#[test]
+pub fn test_randomize_from_density() {
+    let mut universe = Universe::with_dimensions(40, 30).unwrap();
+    let population = universe.randomize_from_density(9, 0.3);
+    assert_eq!(population, universe.population());
+    assert!(population > 0 && population < 40 * 30);
+
+    let mut again = Universe::with_dimensions(40, 30).unwrap();
+    again.randomize(9, 0.3);
+    assert!(again == universe);
+    assert_eq!(universe.randomize_from_density(9, 0.0), 0);
+    assert_eq!(universe.randomize_from_density(9, 1.0), 40 * 30);
+}
+
+#[test]
+pub fn test_randomize_entropy_reports_seed() {
+    let mut universe = Universe::with_dimensions(40, 30).unwrap();
+    assert_eq!(universe.last_seed(), None);
+    universe.randomize_entropy(0.4);
+    let seed = universe.last_seed().unwrap();
+
+    let mut again = Universe::with_dimensions(40, 30).unwrap();
+    again.randomize(seed, 0.4);
+    assert!(again == universe);
+    assert_eq!(again.last_seed(), Some(seed));
+
+    universe.randomize_entropy(0.4);
+    assert_ne!(universe.last_seed(), Some(seed));
+}
+
+#[test]
+pub fn test_two_species() {
+    // Two blinkers of different species keep their colors as they oscillate.
+    let mut universe = Universe::with_dimensions(12, 7).unwrap();
+    universe.insert_blinker(3, 1);
+    universe.insert_blinker(3, 7);
+    for col in 7..10 {
+        universe.set_species(3, col, Cell::AliveB);
+    }
+    universe.tick();
+    let species =
+        |u: &Universe, row: u32, col: u32| u.get_cells()[(row * u.width() + col) as usize];
+    for row in 2..5 {
+        assert_eq!(species(&universe, row, 2), Cell::Alive);
+        assert_eq!(species(&universe, row, 8), Cell::AliveB);
+    }
+    assert_eq!(universe.population(), 6);
+
+    // A birth joins the majority of its three parents.
+    let mut universe = Universe::from_matrix("000\n111\n000").unwrap();
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    universe.set_species(1, 0, Cell::AliveB);
+    universe.set_species(1, 1, Cell::AliveB);
+    let mut sparse = Universe::from_matrix("000\n111\n000").unwrap();
+    sparse.set_boundary_mode(BoundaryMode::Fixed);
+    sparse.set_species(1, 0, Cell::AliveB);
+    sparse.set_species(1, 1, Cell::AliveB);
+    universe.tick();
+    sparse.tick_sparse();
+    assert_eq!(species(&universe, 0, 1), Cell::AliveB);
+    assert_eq!(species(&universe, 2, 1), Cell::AliveB);
+    assert_eq!(species(&universe, 1, 1), Cell::AliveB);
+    assert_eq!(sparse.get_cells(), universe.get_cells());
+
+    let pixels = universe.render_rgba_species(0x11, 0x22, 0x33);
+    assert_eq!(&pixels[0..4], &[0, 0, 0, 0x33]);
+    assert_eq!(&pixels[4..8], &[0, 0, 0, 0x22]);
+}
+
+#[test]
+pub fn test_trim() {
+    let mut universe = Universe::with_dimensions(20, 15).unwrap();
+    universe.insert_glider(4, 9);
+    universe.trim(0).unwrap();
+    assert_eq!((universe.width(), universe.height()), (3, 3));
+    assert_eq!(universe.to_matrix(), "010\n001\n111\n");
+    assert_eq!(
+        Universe::from_rle(&universe.to_rle()).unwrap().population(),
+        5
+    );
+
+    universe.trim(2).unwrap();
+    assert_eq!((universe.width(), universe.height()), (7, 7));
+    assert_eq!(&universe.live_bounds().unwrap()[..], &[2, 2, 4, 4]);
+
+    let mut empty = Universe::with_dimensions(6, 4).unwrap();
+    empty.trim(1).unwrap();
+    assert_eq!((empty.width(), empty.height()), (6, 4));
+}
+
+#[test]
+pub fn test_insert_named() {
+    let mut named = Universe::with_dimensions(20, 20).unwrap();
+    let mut direct = Universe::with_dimensions(20, 20).unwrap();
+    named.insert_named("glider", 2, 3).unwrap();
+    direct.insert_glider(2, 3);
+    named.insert_named("pulsar", 6, 6).unwrap();
+    direct.insert_pulsar(6, 6);
+    assert!(named == direct);
+
+    let mut lwss = Universe::with_dimensions(5, 4).unwrap();
+    lwss.insert_named("lwss", 0, 0).unwrap();
+    assert_eq!(lwss.to_matrix(), "01001\n10000\n10001\n11110\n");
+
+    // The gun repeats every 30 generations, firing one glider each time.
+    let mut gun = UniverseBuilder::new()
+        .width(60)
+        .height(40)
+        .boundary(BoundaryMode::Fixed)
+        .try_build()
+        .unwrap();
+    gun.insert_named("gosper_glider_gun", 1, 1).unwrap();
+    assert_eq!(gun.population(), 36);
+    gun.tick_many(30);
+    assert_eq!(gun.detect_period(60), None);
+    gun.tick_many(90);
+    assert_eq!(gun.population(), 36 + 4 * 5);
+    assert_eq!(gun.find_gliders().len(), 4 * 2);
+}
+
+#[test]
+pub fn test_frozen_cells() {
+    let mut universe = Universe::with_dimensions(7, 7).unwrap();
+    universe.insert_blinker(3, 2);
+    universe.set_frozen(3, 2, true);
+    // A frozen dead cell is never born, though it would be next tick.
+    universe.set_frozen(2, 3, true);
+    universe.tick();
+    assert!(universe.get_cell(3, 2));
+    assert!(!universe.get_cell(2, 3));
+    assert!(universe.get_cell(3, 3) && universe.get_cell(4, 3));
+    assert!(universe.is_frozen(3, 2) && !universe.is_frozen(3, 3));
+
+    // The dense, sparse and tiled ticks agree around a wall.
+    let build = || {
+        let mut universe = UniverseBuilder::new()
+            .width(50)
+            .height(40)
+            .seed(9)
+            .density(0.35)
+            .try_build()
+            .unwrap();
+        for row in 0..40 {
+            universe.set_frozen(row, 20, true);
+        }
+        universe
+    };
+    let (mut dense, mut sparse) = (build(), build());
+    let wall: Vec<bool> = (0..40).map(|row| dense.get_cell(row, 20)).collect();
+    for _ in 0..20 {
+        // Changing the rule forces a full recompute instead of tiles.
+        dense.set_rule("B3/S23").unwrap();
+        dense.tick();
+        sparse.tick_sparse();
+        assert_eq!(sparse.get_cells(), dense.get_cells());
+    }
+    let mut tiled = build();
+    tiled.tick_many(20);
+    assert!(tiled == dense);
+    assert_eq!(
+        (0..40)
+            .map(|row| dense.get_cell(row, 20))
+            .collect::<Vec<_>>(),
+        wall
+    );
+
+    dense.clear();
+    assert!(!dense.is_frozen(0, 20));
+}
+
+#[test]
+pub fn test_diff_against() {
+    let before = Universe::from_matrix("0100\n0010\n1110").unwrap();
+    let mut after = Universe::from_matrix("0100\n0010\n1110").unwrap();
+    assert_eq!(before.diff_against(&after).unwrap(), Vec::<u32>::new());
+
+    after.tick();
+    let diff = before.diff_against(&after).unwrap();
+    assert_eq!(diff, after.changed_cells());
+    assert_eq!(after.diff_against(&before).unwrap(), diff);
+
+    let column_major = UniverseBuilder::new()
+        .width(4)
+        .height(3)
+        .layout(Layout::ColumnMajor)
+        .try_build()
+        .unwrap();
+    assert_eq!(
+        before.diff_against(&column_major).unwrap(),
+        vec![1, 6, 8, 9, 10]
+    );
+}
+
+#[test]
+pub fn test_record_frames() {
+    let mut universe = Universe::new_demo();
+    let mut frames = Vec::new();
+    universe.record(10, 4, |u| frames.push((u.generation(), u.population())));
+    assert_eq!(
+        frames
+            .iter()
+            .map(|&(generation, _)| generation)
+            .collect::<Vec<_>>(),
+        vec![4, 8, 10]
+    );
+
+    let mut replay = Universe::new_demo();
+    replay.tick_many(8);
+    assert_eq!(frames[1].1, replay.population());
+
+    let mut calls = 0;
+    universe.record(6, 3, |_| calls += 1);
+    assert_eq!(calls, 2);
+    universe.record(5, 0, |_| calls += 1);
+    assert_eq!(calls, 3);
+    universe.record(0, 2, |_| calls += 1);
+    assert_eq!(calls, 3);
+}
+
+#[test]
+pub fn test_is_empty_and_is_full() {
+    let mut universe = Universe::with_dimensions(4, 3).unwrap();
+    assert!(universe.is_empty() && !universe.is_full());
+    universe.toggle_cell(2, 3);
+    assert!(!universe.is_empty() && !universe.is_full());
+    universe.invert();
+    universe.toggle_cell(2, 3);
+    assert!(!universe.is_empty() && universe.is_full());
+    universe.set_species(0, 0, Cell::AliveB);
+    assert!(universe.is_full());
+}
+
+#[test]
+pub fn test_twisted_torus() {
+    // A cell on the bottom row sees the top row shifted by the twist.
+    let mut universe = Universe::with_dimensions(8, 4).unwrap();
+    universe.toggle_cell(0, 5);
+    assert_eq!(universe.neighbors_alive(3, 6), 1);
+    assert_eq!(universe.neighbors_alive(3, 2), 0);
+    universe.set_twist(2);
+    assert_eq!(universe.neighbors_alive(3, 6), 0);
+    assert_eq!(universe.neighbors_alive(3, 2), 1);
+    // And the top row sees the bottom shifted back.
+    universe.toggle_cell(0, 5);
+    universe.toggle_cell(3, 2);
+    assert_eq!(universe.neighbors_alive(0, 4), 1);
+    assert_eq!(universe.neighbors_alive(0, 1), 0);
+    universe.set_twist(0);
+    assert_eq!(universe.neighbors_alive(0, 1), 1);
+
+    // A glider heading down crosses the bottom edge and comes back in
+    // shifted, intact; every tick path agrees on the way.
+    let build = || {
+        let mut universe = UniverseBuilder::new()
+            .width(40)
+            .height(36)
+            .twist(-7)
+            .try_build()
+            .unwrap();
+        universe.insert_glider(30, 3);
+        universe
+    };
+    let (mut dense, mut sparse) = (build(), build());
+    let mut packed = PackedUniverse::from_universe(&dense).unwrap();
+    for _ in 0..40 {
+        dense.tick();
+        sparse.tick_sparse();
+        packed.tick();
+        assert_eq!(sparse.get_cells(), dense.get_cells());
+        assert_eq!(packed.population(), dense.population());
+    }
+    // Ten cells down and right from (30, 3), less the twist for the wrap.
+    assert_eq!(dense.find_gliders(), vec![4, 6]);
+
+    // Scrolling follows the twisted wrap too.
+    let mut shifted = build();
+    shifted.shift(10, 10);
+    assert_eq!(shifted.find_gliders(), vec![4, 6]);
+}
+
+#[test]
+pub fn test_density_map() {
+    let universe = Universe::from_matrix("11000\n10000\n00001").unwrap();
+    // 2×2 blocks: three across (the last one column wide) and two down
+    // (the last one row high).
+    assert_eq!(
+        universe.density_map(2).unwrap(),
+        vec![0.75, 0.0, 0.0, 0.0, 0.0, 1.0]
+    );
+    assert_eq!(universe.density_map(10).unwrap(), vec![4.0 / 15.0]);
+    assert_eq!(
+        universe
+            .density_map(1)
+            .unwrap()
+            .iter()
+            .filter(|&&d| d == 1.0)
+            .count(),
+        4
+    );
+}
+
+#[test]
+pub fn test_stamp_rle() {
+    let mut universe = Universe::with_dimensions(6, 6).unwrap();
+    universe
+        .stamp_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!", 1, 2)
+        .unwrap();
+    let mut expected = Universe::with_dimensions(6, 6).unwrap();
+    expected.insert_glider(1, 2);
+    assert!(universe == expected);
+
+    // Past the corner it wraps on a torus and clips on a fixed board.
+    universe.clear();
+    universe.stamp_rle("x = 2, y = 2\n2o$2o!", 5, 5).unwrap();
+    assert_eq!(universe.live_coords(), vec![0, 0, 0, 5, 5, 0, 5, 5]);
+    universe.clear();
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    universe.stamp_rle("x = 2, y = 2\n2o$2o!", 5, 5).unwrap();
+    assert_eq!(universe.live_coords(), vec![5, 5]);
+}
+
+#[test]
+pub fn test_copy_paste_region_round_trip() {
+    // A 2×2 region across the corner wraps on a torus...
+    let mut source = Universe::with_dimensions(6, 6).unwrap();
+    source.set_cell(5, 5, true);
+    source.set_cell(0, 0, true);
+    let region = source.copy_region(5, 5, 2, 2);
+    assert_eq!(region, vec![1, 0, 0, 1]);
+    let mut target = Universe::with_dimensions(6, 6).unwrap();
+    target.paste_region(5, 5, 2, 2, &region);
+    assert!(target == source);
+
+    // ...while on a fixed board the part past the edge reads as dead and
+    // pastes nowhere.
+    source.set_boundary_mode(BoundaryMode::Fixed);
+    let region = source.copy_region(5, 5, 2, 2);
+    assert_eq!(region, vec![1, 0, 0, 0]);
+    let mut target = Universe::with_dimensions(6, 6).unwrap();
+    target.set_boundary_mode(BoundaryMode::Fixed);
+    target.paste_region(5, 5, 2, 2, &[1, 1, 1, 1]);
+    assert_eq!(target.live_coords(), vec![5, 5]);
+    target.paste_region(1, 1, 2, 2, &source.copy_region(4, 4, 2, 2));
+    assert_eq!(target.live_coords(), vec![2, 2, 5, 5]);
+}
+
+#[test]
+pub fn test_stamp_rle_keeps_species() {
+    let mut universe = Universe::with_dimensions(4, 4).unwrap();
+    universe.stamp_rle("x = 3, y = 1\nA.B!", 1, 0).unwrap();
+    assert_eq!(
+        &universe.get_cells()[4..8],
+        &[Cell::Alive, Cell::Dead, Cell::AliveB, Cell::Dead]
+    );
+}
+
+#[test]
+pub fn test_render_rgba_generations() {
+    // Brian's Brain: every live cell spends one generation dying.
+    let mut universe = Universe::from_matrix("000\n010\n000").unwrap();
+    universe.set_rule("B2/S").unwrap();
+    universe.set_num_states(3);
+    let gradient = [0xff0000ff, 0x00ff00ff, 0x0000ffff];
+    let pixels = universe.render_rgba_generations(&gradient).unwrap();
+    assert_eq!(pixels.len(), 9 * 4);
+    assert_eq!(&pixels[16..20], &[0xff, 0, 0, 0xff]);
+    assert_eq!(&pixels[0..4], &[0, 0, 0xff, 0xff]);
+
+    universe.tick();
+    let pixels = universe.render_rgba_generations(&gradient).unwrap();
+    assert_eq!(&pixels[16..20], &[0, 0xff, 0, 0xff]);
+
+    // With two states it is just alive and dead.
+    let plain = Universe::from_matrix("10").unwrap();
+    let pixels = plain.render_rgba_generations(&[1, 2]).unwrap();
+    assert_eq!(pixels, vec![0, 0, 0, 1, 0, 0, 0, 2]);
+}
+
+#[test]
+pub fn test_von_neumann_neighborhood() {
+    let mut universe = Universe::from_matrix("101\n010\n101").unwrap();
+    assert_eq!(universe.neighborhood(), Neighborhood::Moore);
+    assert_eq!(universe.neighbors_alive(1, 1), 4);
+    assert_eq!(universe.neighbors_alive(0, 1), 5);
+    universe.set_neighborhood(Neighborhood::VonNeumann);
+    assert_eq!(universe.neighbors_alive(1, 1), 0);
+    assert_eq!(universe.neighbors_alive(0, 1), 3);
+
+    universe.set_radius(2);
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    // Radius 2 reaches the diagonals, two steps away.
+    assert_eq!(universe.neighbors_alive(1, 1), 4);
+    assert_eq!(universe.neighbors_alive(0, 0), 3);
+
+    // Birth on any neighbor grows a diamond from a single cell; the sparse
+    // tick falls back to the dense one and agrees with it.
+    let build = || {
+        let mut universe = UniverseBuilder::new()
+            .width(11)
+            .height(11)
+            .rule("B1234/S01234")
+            .neighborhood(Neighborhood::VonNeumann)
+            .try_build()
+            .unwrap();
+        universe.toggle_cell(5, 5);
+        universe
+    };
+    let (mut dense, mut sparse) = (build(), build());
+    for _ in 0..3 {
+        dense.tick();
+        sparse.tick_sparse();
+    }
+    assert!(dense == sparse);
+    assert_eq!(dense.population(), 25);
+    assert_eq!(&dense.live_bounds().unwrap()[..], &[2, 2, 8, 8]);
+    assert!(!dense.get_cell(2, 2) && dense.get_cell(2, 5));
+}
+
+#[test]
+pub fn test_cells_mut_ptr_and_mark_dirty() {
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.insert_blinker(1, 1);
+    // Let the sparse and tiled ticks build up their incremental state.
+    universe.tick_sparse();
+    universe.tick();
+    let before = universe.snapshot();
+
+    let ptr = universe.cells_mut_ptr();
+    let len = universe.cells_len() as usize;
+    unsafe {
+        let cells = std::slice::from_raw_parts_mut(ptr as *mut u8, len);
+        for &(row, col) in &[(5, 4), (5, 5), (5, 6)] {
+            cells[row * 8 + col] = 1;
+        }
+    }
+    universe.mark_dirty();
+    assert_eq!(universe.population(), 6);
+
+    let mut expected = Universe::with_dimensions(8, 8).unwrap();
+    expected.insert_blinker(1, 1);
+    expected.tick();
+    expected.tick();
+    expected.insert_blinker(5, 4);
+    universe.tick_sparse();
+    expected.tick();
+    assert!(universe == expected);
+
+    // The snapshot taken before the writes still holds the old board.
+    universe.restore(&before).unwrap();
+    assert_eq!(universe.population(), 3);
+    assert!(universe.undo().unwrap());
+    assert_eq!(universe.population(), 6);
+}
+
+#[test]
+pub fn test_count_components() {
+    let mut universe = Universe::from_matrix("11000\n10010\n00100\n00000\n00001").unwrap();
+    // On the torus the bottom-right corner touches the top-left block.
+    assert_eq!(universe.count_components(true), 2);
+    assert_eq!(universe.count_components(false), 4);
+    universe.set_boundary_mode(BoundaryMode::Fixed);
+    assert_eq!(universe.count_components(true), 3);
+    assert_eq!(universe.count_components(false), 4);
+
+    universe.set_boundary_mode(BoundaryMode::Toroidal);
+    universe.toggle_cell(0, 0);
+    assert_eq!(universe.count_components(true), 3);
+
+    assert_eq!(
+        Universe::with_dimensions(4, 4)
+            .unwrap()
+            .count_components(true),
+        0
+    );
+}
+
+#[test]
+fn test_resize_works_again_once_a_tick_finishes() {
+    let mut universe = Universe::with_dimensions(6, 4).unwrap();
+    universe.tick();
+    universe.tick_sparse();
+    assert!(universe.resize(8, 5).is_ok());
+    universe.set_width(3).unwrap();
+    assert_eq!((universe.width(), universe.height()), (3, 5));
+}
+
+#[test]
+fn test_detect_period_keeps_ages() {
+    let mut universe = Universe::with_dimensions(5, 5).unwrap();
+    universe.set_cells(&[(2, 1), (2, 2), (2, 3)]);
+    universe.tick_many(2);
+    let ages = |u: &Universe| {
+        (0..5)
+            .flat_map(|row| (0..5).map(move |col| (row, col)))
+            .map(|(row, col)| u.get_age(row, col))
+            .collect::<Vec<_>>()
+    };
+    let before = ages(&universe);
+    assert_eq!(universe.get_age(2, 2), 2);
+    assert_eq!(universe.detect_period(4), Some(2));
+    assert_eq!(ages(&universe), before);
+}
+
+#[test]
+fn test_detect_period_keeps_states() {
+    let mut universe = Universe::from_matrix("00000\n00000\n01110\n00000\n00000").unwrap();
+    universe.set_num_states(3);
+    universe.tick();
+    let states = |u: &Universe| {
+        (0..5)
+            .flat_map(|row| (0..5).map(move |col| (row, col)))
+            .map(|(row, col)| u.get_state(row, col))
+            .collect::<Vec<_>>()
+    };
+    let before = states(&universe);
+    assert!(before.iter().any(|&state| state > 1));
+    universe.detect_period(4);
+    assert_eq!(states(&universe), before);
+}
+
+#[test]
+fn test_rle_round_trip_keeps_rule() {
+    let mut universe = Universe::from_matrix("000000\n011100\n000000").unwrap();
+    universe.set_rule("B36/S23").unwrap();
+    let rle = universe.to_rle();
+    assert!(rle.starts_with("x = 6, y = 3, rule = B36/S23\n"));
+    assert_eq!(Universe::from_rle(&rle).unwrap().to_rle(), rle);
+}
+
+#[cfg(not(feature = "rayon"))]
+#[test]
+fn test_tick_hook_runs_after_each_tick() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let generations = Rc::new(RefCell::new(Vec::new()));
+    let seen = generations.clone();
+    let mut universe = Universe::new_demo();
+    universe.set_tick_hook(move |universe, ms| {
+        assert!(ms >= 0.0);
+        seen.borrow_mut().push(universe.generation());
+    });
+    universe.tick();
+    universe.tick_sparse();
+    universe.tick_many(3);
+    universe.tick_until_stable(2);
+    universe.tick_region(0, 0, 8, 8);
+    universe.clear_tick_hook();
+    universe.tick();
+    assert_eq!(*generations.borrow(), vec![1, 2, 5, 7, 8]);
+}
+
+#[test]
+fn test_zero_step_ticks_keep_redo() {
+    let mut universe = input_spaceship();
+    universe.tick();
+    assert!(universe.undo().unwrap());
+    universe.tick_many(0);
+    assert_eq!(universe.tick_until_stable(0), 0);
+    assert_eq!(universe.generation(), 0);
+    assert!(universe.redo().unwrap());
+    assert_eq!(&universe.get_cells(), &expected_spaceship().get_cells());
+}
+
+#[test]
+fn test_species_in_exports() {
+    let mut universe = Universe::with_dimensions(4, 2).unwrap();
+    universe.set_species(0, 0, Cell::Alive);
+    universe.set_species(0, 1, Cell::AliveB);
+    universe.set_species(1, 3, Cell::AliveB);
+    let species = |u: &Universe| (0..8).map(|idx| u.get_cells()[idx]).collect::<Vec<_>>();
+
+    let rle = universe.to_rle();
+    assert_eq!(rle, "x = 4, y = 2, rule = B3/S23\nAB$3.B!\n");
+    assert_eq!(
+        species(&Universe::from_rle(&rle).unwrap()),
+        species(&universe)
+    );
+    assert_eq!(universe.to_bytes(), vec![1, 2, 0, 0, 0, 0, 0, 2]);
+    let bytes = Universe::from_bytes(4, 2, &universe.to_bytes()).unwrap();
+    assert_eq!(species(&bytes), species(&universe));
+
+    // The 0/1 formats keep only which cells are alive.
+    let matrix = Universe::from_matrix(&universe.to_matrix()).unwrap();
+    assert_eq!(matrix.to_bytes(), vec![1, 1, 0, 0, 0, 0, 0, 1]);
+    let base64 = Universe::from_base64(&universe.to_base64()).unwrap();
+    assert_eq!(base64.to_bytes(), vec![1, 1, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_packed_follows_the_universe_rule() {
+    let mut dense = Universe::new_demo();
+    dense.set_rule("B36/S23").unwrap();
+    dense.set_boundary_mode(BoundaryMode::Fixed);
+    let mut packed = PackedUniverse::from_universe(&dense).unwrap();
+    for _ in 0..8 {
+        dense.tick();
+        packed.tick();
+    }
+    assert_eq!(packed.population(), dense.population());
+}
+
+#[test]
+fn test_parsed_universe_tracks_its_buffer() {
+    let mut universe = Universe::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    let buffer = (universe.cells(), universe.buffer_generation());
+    universe.tick();
+    universe.undo().unwrap();
+    assert_eq!((universe.cells(), universe.buffer_generation()), buffer);
+}
+
+#[test]
+fn test_mark_dirty_records_one_undo_step() {
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.insert_blinker(1, 1);
+    universe.tick();
+    // A render loop fetching the pointer every frame adds nothing to undo.
+    for _ in 0..3 {
+        universe.cells_mut_ptr();
+    }
+    let ptr = universe.cells_mut_ptr();
+    unsafe { *(ptr as *mut u8).add(63) = 1 };
+    universe.mark_dirty();
+    assert_eq!(universe.population(), 4);
+
+    assert!(universe.undo().unwrap());
+    assert_eq!(universe.population(), 3);
+    assert_eq!(universe.generation(), 1);
+    assert!(universe.undo().unwrap());
+    assert_eq!(universe.generation(), 0);
+    // Then the blinker's insertion.
+    assert!(universe.undo().unwrap());
+    assert_eq!(universe.population(), 0);
+    assert!(!universe.undo().unwrap());
+}
+
+#[test]
+fn test_noise_in_generations_mode() {
+    // Brian's Brain with every cell flipped each generation: the dying
+    // cells come back plainly alive, and die again from state 2.
+    let mut universe = Universe::from_matrix("000\n010\n000").unwrap();
+    universe.set_rule("B2/S").unwrap();
+    universe.set_num_states(3);
+    universe.set_noise(1, 1.0);
+    universe.tick();
+    assert_eq!(universe.population(), 9);
+    assert_eq!(universe.get_state(1, 1), 1);
+
+    universe.set_noise(1, 0.0);
+    universe.tick();
+    assert_eq!(universe.population(), 0);
+    assert_eq!(universe.get_state(1, 1), 2);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_large_boards_keep_the_parallel_tick() {
+    let build = || {
+        let mut universe = Universe::with_dimensions(300, 300).unwrap();
+        universe.insert_glider(1, 1);
+        universe.insert_glider(150, 200);
+        universe
+    };
+    let (mut dense, mut sparse) = (build(), build());
+    for _ in 0..20 {
+        dense.tick();
+        sparse.tick_sparse();
+        assert_eq!(dense.get_cells(), sparse.get_cells());
+    }
+    // Every 32×32 tile is still recomputed, in parallel, rather than only
+    // the two around the gliders.
+    assert_eq!(dense.active_tile_count(), 10 * 10);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_parallel_tick_matches_serial() {
+    // `tick_region` over the whole board steps it cell by cell on one
+    // thread, while `tick` splits a board this size across threads.
+    let configs: [(BoundaryMode, Layout, &str, u32); 3] = [
+        (BoundaryMode::Toroidal, Layout::RowMajor, "B3/S23", 1),
+        (BoundaryMode::Fixed, Layout::ColumnMajor, "B36/S23", 1),
+        (
+            BoundaryMode::CylinderHorizontal,
+            Layout::RowMajor,
+            "B34/S34",
+            2,
+        ),
+    ];
+    for &(boundary, layout, rule, radius) in configs.iter() {
+        let build = || {
+            let mut universe = UniverseBuilder::new()
+                .width(256)
+                .height(300)
+                .boundary(boundary)
+                .layout(layout)
+                .rule(rule)
+                .try_build()
+                .unwrap();
+            universe.set_radius(radius);
+            universe.randomize(7, 0.3);
+            universe
+        };
+        let (mut parallel, mut serial) = (build(), build());
+        for _ in 0..5 {
+            parallel.tick();
+            serial.tick_region(0, 0, 300, 256);
+            assert_eq!(parallel.get_cells(), serial.get_cells());
+            assert_eq!(parallel.changed_cells(), serial.changed_cells());
+        }
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
+#[test]
+fn test_panicking_tick_hook_ends_the_tick() {
+    let mut universe = Universe::with_dimensions(6, 4).unwrap();
+    universe.set_tick_hook(|_, _| panic!("hook failed"));
+    let ticked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| universe.tick()));
+    assert!(ticked.is_err());
+
+    universe.clear_tick_hook();
+    assert!(universe.resize(8, 8).is_ok());
+    assert_eq!((universe.width(), universe.height()), (8, 8));
+}
+
+#[test]
+fn test_insert_pattern_resets_age_and_state() {
+    let mut universe = Universe::with_dimensions(6, 6).unwrap();
+    universe.insert_pattern(1, 1, 2, 2, &[1, 1, 1, 1]);
+    universe.tick_many(3);
+    assert_eq!(universe.get_age(1, 1), 3);
+    universe.insert_pattern(1, 1, 1, 1, &[0]);
+    assert_eq!(universe.get_age(1, 1), 0);
+    universe.insert_pattern(1, 1, 1, 1, &[1]);
+    assert_eq!(universe.get_age(1, 1), 0);
+
+    // Under B/S with three states the block dies into state 2; stamping
+    // over a dying cell makes it plainly alive.
+    universe.set_rule("B/S").unwrap();
+    universe.set_num_states(3);
+    universe.tick();
+    assert_eq!(universe.get_state(1, 2), 2);
+    universe.insert_glider(0, 0);
+    assert_eq!(universe.get_state(1, 2), 1);
+    universe.insert_pattern(1, 2, 1, 1, &[0]);
+    assert_eq!(universe.get_state(1, 2), 0);
+}
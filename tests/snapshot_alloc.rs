@@ -0,0 +1,50 @@
+//! Checks that snapshots of an unchanged board share their cells. Kept in
+//! its own test binary because it counts every allocation the process makes.
+
+// `wee_alloc` installs its own global allocator.
+#![cfg(not(feature = "wee_alloc"))]
+
+extern crate wasm_game_of_life;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use wasm_game_of_life::Universe;
+
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+pub fn test_snapshots_share_cells() {
+    let mut universe = Universe::with_dimensions(256, 256).unwrap();
+    let board_bytes = 256 * 256;
+    let mut snapshots = Vec::with_capacity(100);
+
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    for _ in 0..100 {
+        snapshots.push(universe.snapshot());
+    }
+    let allocated = ALLOCATED.load(Ordering::SeqCst) - before;
+
+    assert!(
+        allocated < 2 * board_bytes,
+        "100 snapshots allocated {} bytes",
+        allocated
+    );
+    assert_eq!(snapshots.len(), 100);
+}
@@ -6,15 +6,15 @@ extern crate wasm_bindgen_test;
 extern crate wasm_game_of_life;
 
 use wasm_bindgen_test::*;
-use wasm_game_of_life::Universe;
+use wasm_game_of_life::{PackedUniverse, Universe};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
 #[cfg(test)]
 pub fn input_spaceship() -> Universe {
     let mut universe = Universe::new();
-    universe.set_width(6);
-    universe.set_height(6);
+    universe.set_width(6).unwrap();
+    universe.set_height(6).unwrap();
     universe.set_cells(&[(1,2), (2,3), (3,1), (3,2), (3,3)]);
     universe
 }
@@ -22,8 +22,8 @@ pub fn input_spaceship() -> Universe {
 #[cfg(test)]
 pub fn expected_spaceship() -> Universe {
     let mut universe = Universe::new();
-    universe.set_width(6);
-    universe.set_height(6);
+    universe.set_width(6).unwrap();
+    universe.set_height(6).unwrap();
     universe.set_cells(&[(2,1), (2,3), (3,2), (3,3), (4,2)]);
     universe
 }
@@ -40,4 +40,224 @@ pub fn test_tick() {
     // Call `tick` and then see if the cells in the `Universe`s are the same.
     input_universe.tick();
     assert_eq!(&input_universe.get_cells(), &expected_universe.get_cells());
-}
\ No newline at end of file
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_rle() {
+    let universe =
+        Universe::from_rle("#N Glider\nx = 6, y = 6, rule = B3/S23\n$2bo$3bo$b3o!").unwrap();
+    assert_eq!(universe.width(), 6);
+    assert_eq!(universe.height(), 6);
+    assert_eq!(&universe.get_cells(), &input_spaceship().get_cells());
+}
+
+#[wasm_bindgen_test]
+pub fn test_rle_round_trip() {
+    let mut universe = Universe::new();
+    universe.set_width(40).unwrap();
+    universe.set_height(12).unwrap();
+    universe.randomize(7, 0.4);
+
+    let decoded = Universe::from_rle(&universe.to_rle()).unwrap();
+    assert_eq!(decoded.width(), 40);
+    assert_eq!(decoded.height(), 12);
+    assert_eq!(&decoded.get_cells(), &universe.get_cells());
+}
+
+#[wasm_bindgen_test]
+pub fn test_packed_tick() {
+    let mut packed = PackedUniverse::from_universe(&input_spaceship()).unwrap();
+    packed.tick();
+
+    let expected = expected_spaceship();
+    for row in 0..6 {
+        for col in 0..6 {
+            assert_eq!(packed.get_cell(row, col), expected.get_cell(row, col));
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+pub fn test_json_round_trip() {
+    let mut universe = input_spaceship();
+    universe.tick();
+
+    let restored = Universe::from_json(&universe.to_json()).unwrap();
+    assert_eq!(restored.generation(), 1);
+    assert_eq!(&restored.get_cells(), &universe.get_cells());
+}
+
+#[wasm_bindgen_test]
+pub fn test_rejects_zero_dimensions() {
+    assert!(Universe::with_dimensions(0, 10).is_err());
+
+    let mut universe = Universe::with_dimensions(4, 4).unwrap();
+    assert!(universe.resize(4, 0).is_err());
+    assert_eq!(universe.height(), 4);
+    assert!(universe.set_width(0).is_err());
+    assert!(universe.set_height(0).is_err());
+    assert_eq!((universe.width(), universe.height()), (4, 4));
+
+    assert!(PackedUniverse::new(0, 4).is_err());
+    assert!(Universe::from_rle("x = 0, y = 3\n!").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_toggle_cells() {
+    let mut universe = Universe::with_dimensions(4, 4).unwrap();
+    assert!(universe.toggle_cells(&[1, 2, 3]).is_err());
+    assert_eq!(universe.population(), 0);
+
+    universe.toggle_cells(&[1, 2, 3, 0, 1, 2, 9, 9]).unwrap();
+    assert_eq!(universe.population(), 1);
+    assert!(universe.get_cell(3, 0));
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_matrix_rejects_bad_input() {
+    assert!(Universe::from_matrix("010\n11\n000").is_err());
+    assert!(Universe::from_matrix("012").is_err());
+    assert!(Universe::from_matrix("").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_coords_rejects_bad_input() {
+    assert!(Universe::from_coords(4, 4, &[1, 2, 3]).is_err());
+    assert!(Universe::from_coords(4, 4, &[1, 4]).is_err());
+    assert!(Universe::from_coords(0, 4, &[]).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_bytes_rejects_bad_input() {
+    assert!(Universe::from_bytes(2, 2, &[0, 1, 0]).is_err());
+    assert!(Universe::from_bytes(2, 2, &[0, 1, 3, 0]).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_block_entropy_rejects_uneven_blocks() {
+    let universe = Universe::with_dimensions(6, 4).unwrap();
+    assert!(universe.block_entropy(4).is_err());
+    assert!(universe.block_entropy(0).is_err());
+    assert!(universe.block_entropy(2).is_ok());
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_rle_error_position() {
+    let err = match Universe::from_rle("#C comment\nx = 3, y = 1\nbo\n bxo!") {
+        Ok(_) => panic!("malformed RLE was accepted"),
+        Err(err) => err.as_string().unwrap(),
+    };
+    assert_eq!(err, "unexpected character 'x' at line 4 col 3");
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_life106_rejects_bad_input() {
+    assert!(Universe::from_life106("#Life 1.06\n0 0\n5 0\n", 4, 4, false).is_err());
+    assert!(Universe::from_life106("#Life 1.06\n0 zero\n", 4, 4, true).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_base64_rejects_bad_input() {
+    assert!(Universe::from_base64("AQAAAAEAAAA").is_err());
+    assert!(Universe::from_base64("AQAAAAEAAAAB!").is_err());
+    assert!(Universe::from_base64("AAAAAAEAAAAB").is_err());
+    // Claims a 20000x20000 board with no cell bytes.
+    assert!(Universe::from_base64("IE4AACBOAAA").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_insert_named_rejects_unknown_name() {
+    let mut universe = Universe::new();
+    assert!(universe.insert_named("glider", 0, 0).is_ok());
+    assert!(universe.insert_named("no_such_pattern", 0, 0).is_err());
+}
+
+#[wasm_bindgen_test]
+#[cfg(not(feature = "rayon"))]
+pub fn test_tick_callback_receives_durations() {
+    let calls = js_sys::Array::new();
+    let record = js_sys::Function::new_with_args("ms", "this.push(ms)").bind0(&calls);
+    let mut universe = Universe::new();
+    universe.set_tick_callback(record);
+    universe.tick();
+    universe.tick_sparse();
+    assert_eq!(calls.length(), 2);
+    assert!(calls.get(0).as_f64().unwrap() >= 0.0);
+
+    universe.clear_tick_callback();
+    universe.tick();
+    assert_eq!(calls.length(), 2);
+}
+
+#[wasm_bindgen_test]
+pub fn test_diff_against_rejects_mismatched_sizes() {
+    let small = Universe::with_dimensions(4, 4).unwrap();
+    let large = Universe::with_dimensions(5, 4).unwrap();
+    assert!(small.diff_against(&large).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_density_map_rejects_zero_tile() {
+    assert!(Universe::new().density_map(0).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_stamp_rle_rejects_bad_rle() {
+    let mut universe = Universe::new();
+    assert!(universe.stamp_rle("bo$2bo$3o!", 0, 0).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_render_rgba_generations_rejects_short_gradient() {
+    let mut universe = Universe::new();
+    universe.set_num_states(4);
+    assert!(universe.render_rgba_generations(&[1, 2, 3]).is_err());
+    assert!(universe.render_rgba_generations(&[1, 2, 3, 4]).is_ok());
+}
+
+#[cfg(not(feature = "rayon"))]
+#[wasm_bindgen_test]
+pub fn test_resize_from_tick_hook_is_rejected() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let seen = errors.clone();
+    let mut universe = Universe::with_dimensions(6, 4).unwrap();
+    universe.set_tick_hook(move |universe, _| {
+        // A nested tick doesn't lift the outer tick's guard when it ends.
+        universe.tick();
+        let mut seen = seen.borrow_mut();
+        seen.push(universe.resize(12, 12).unwrap_err().as_string().unwrap());
+        seen.push(universe.set_width(12).unwrap_err().as_string().unwrap());
+        seen.push(universe.rotate_cw().unwrap_err().as_string().unwrap());
+        seen.push(universe.undo().unwrap_err().as_string().unwrap());
+    });
+    universe.tick();
+    assert_eq!(
+        *errors.borrow(),
+        vec!["cannot change the board while a tick is in progress"; 4]
+    );
+    assert_eq!((universe.width(), universe.height()), (6, 4));
+
+    // The guard only lasts for the tick itself.
+    universe.clear_tick_hook();
+    universe.resize(12, 12).unwrap();
+    assert_eq!((universe.width(), universe.height()), (12, 12));
+}
+
+#[wasm_bindgen_test]
+pub fn test_from_universe_rejects_unsupported_settings() {
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.set_radius(2);
+    assert!(PackedUniverse::from_universe(&universe).is_err());
+
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.set_num_states(3);
+    assert!(PackedUniverse::from_universe(&universe).is_err());
+
+    let mut universe = Universe::with_dimensions(8, 8).unwrap();
+    universe.set_frozen(1, 1, true);
+    assert!(PackedUniverse::from_universe(&universe).is_err());
+}